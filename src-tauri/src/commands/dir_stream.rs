@@ -0,0 +1,211 @@
+use super::fs_ops::open_directory;
+use crate::listing::{prepare, Candidate};
+use crate::models::{FileEntry, ListOptions};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::ipc::Channel;
+
+/// 最初のバッチは 1 画面分だけにして、すぐ表示できるようにする
+const FIRST_BATCH: usize = 100;
+const BATCH: usize = 1000;
+
+/// フォルダの読み込み中にチャンネルへ送るイベント
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DirectoryEvent {
+    /// 読み込んだ項目（`ListOptions` に従った順）
+    Entries { entries: Vec<FileEntry> },
+    /// すべて送り終えた。キャンセルした場合は送らない
+    Done { total: usize },
+}
+
+#[derive(Default)]
+struct StreamsState {
+    next_id: u32,
+    active: HashMap<u32, Arc<AtomicBool>>,
+}
+
+/// 読み込み中のフォルダ（キャンセル用）
+#[derive(Clone, Default)]
+pub struct DirectoryStreams {
+    state: Arc<Mutex<StreamsState>>,
+}
+
+impl DirectoryStreams {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn start(&self) -> (u32, Arc<AtomicBool>) {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let id = state.next_id;
+        let cancelled = Arc::new(AtomicBool::new(false));
+        state.active.insert(id, cancelled.clone());
+        (id, cancelled)
+    }
+
+    fn finish(&self, id: u32) {
+        self.state.lock().unwrap().active.remove(&id);
+    }
+
+    /// 読み込みを止める。既に終わっていれば何もしない
+    pub fn cancel(&self, id: u32) {
+        if let Some(cancelled) = self.state.lock().unwrap().active.remove(&id) {
+            cancelled.store(true, Ordering::SeqCst);
+        }
+    }
+}
+
+/// フォルダの中身を `read_directory` と同じ順でバッチに分けて `on_event` へ送る。
+/// 名前・種類で並べる場合、各項目のメタデータはそのバッチを送る直前に読むので、
+/// 巨大なフォルダでも最初の 1 画面はすぐ届く。
+/// 戻り値のストリーム ID を `cancel_directory_stream` に渡すと途中で止められる
+#[tauri::command]
+pub fn read_directory_stream(
+    streams: tauri::State<'_, DirectoryStreams>,
+    path: String,
+    options: Option<ListOptions>,
+    on_event: Channel<DirectoryEvent>,
+) -> Result<u32, String> {
+    let reader = open_directory(&path)?;
+    let options = options.unwrap_or_default();
+    // パターンの誤りなどは呼び出し元へ返したいので、ここで検証する
+    crate::listing::validate(&options)?;
+    let streams = streams.inner().clone();
+    let (id, cancelled) = streams.start();
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let stop = || cancelled.load(Ordering::SeqCst);
+        if let Ok(Some(candidates)) = prepare(reader, &options, stop) {
+            stream_entries(candidates, &cancelled, |event| on_event.send(event).is_ok());
+        }
+        streams.finish(id);
+    });
+    Ok(id)
+}
+
+#[tauri::command]
+pub fn cancel_directory_stream(streams: tauri::State<'_, DirectoryStreams>, stream_id: u32) {
+    streams.cancel(stream_id);
+}
+
+/// `send` が false を返したら（受け取り側がいなくなったら）やめる
+fn stream_entries<F>(candidates: Vec<Candidate>, cancelled: &AtomicBool, mut send: F)
+where
+    F: FnMut(DirectoryEvent) -> bool,
+{
+    let mut batch = Vec::with_capacity(FIRST_BATCH);
+    let mut limit = FIRST_BATCH;
+    let mut total = 0;
+
+    for candidate in candidates {
+        if cancelled.load(Ordering::SeqCst) {
+            return;
+        }
+        let Some(file) = candidate.into_entry() else {
+            continue;
+        };
+        batch.push(file);
+        if batch.len() >= limit {
+            total += batch.len();
+            let entries = std::mem::replace(&mut batch, Vec::with_capacity(BATCH));
+            if !send(DirectoryEvent::Entries { entries }) {
+                return;
+            }
+            limit = BATCH;
+        }
+    }
+    if cancelled.load(Ordering::SeqCst) {
+        return;
+    }
+    if !batch.is_empty() {
+        total += batch.len();
+        if !send(DirectoryEvent::Entries { entries: batch }) {
+            return;
+        }
+    }
+    send(DirectoryEvent::Done { total });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn candidates(dir: &std::path::Path) -> Vec<Candidate> {
+        prepare(fs::read_dir(dir).unwrap(), &ListOptions::default(), || {
+            false
+        })
+        .unwrap()
+        .unwrap()
+    }
+
+    fn populate(count: usize) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..count {
+            fs::write(dir.path().join(format!("f{}.txt", i)), "").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn sends_a_small_first_batch_then_larger_ones() {
+        let dir = populate(FIRST_BATCH + BATCH + 5);
+        let mut sizes = Vec::new();
+        let mut done = None;
+        stream_entries(candidates(dir.path()), &AtomicBool::new(false), |event| {
+            match event {
+                DirectoryEvent::Entries { entries } => sizes.push(entries.len()),
+                DirectoryEvent::Done { total } => done = Some(total),
+            }
+            true
+        });
+        assert_eq!(sizes, [FIRST_BATCH, BATCH, 5]);
+        assert_eq!(done, Some(FIRST_BATCH + BATCH + 5));
+    }
+
+    #[test]
+    fn empty_directory_only_sends_done() {
+        let dir = populate(0);
+        let mut events = Vec::new();
+        stream_entries(candidates(dir.path()), &AtomicBool::new(false), |event| {
+            events.push(event);
+            true
+        });
+        assert!(matches!(events[..], [DirectoryEvent::Done { total: 0 }]));
+    }
+
+    #[test]
+    fn stops_when_cancelled_or_receiver_is_gone() {
+        let dir = populate(FIRST_BATCH * 3);
+        let cancelled = AtomicBool::new(false);
+        let mut batches = 0;
+        stream_entries(candidates(dir.path()), &cancelled, |_| {
+            batches += 1;
+            cancelled.store(true, Ordering::SeqCst);
+            true
+        });
+        assert_eq!(batches, 1);
+
+        let mut sent = 0;
+        stream_entries(candidates(dir.path()), &AtomicBool::new(false), |_| {
+            sent += 1;
+            false
+        });
+        assert_eq!(sent, 1);
+    }
+
+    #[test]
+    fn cancel_marks_only_the_given_stream() {
+        let streams = DirectoryStreams::new();
+        let (first, first_flag) = streams.start();
+        let (_, second_flag) = streams.start();
+        streams.cancel(first);
+        streams.cancel(first);
+        assert!(first_flag.load(Ordering::SeqCst));
+        assert!(!second_flag.load(Ordering::SeqCst));
+    }
+}
@@ -1,9 +1,33 @@
-use crate::models::FileEntry;
+use super::rename_ops::{rename_all, rename_no_replace};
+use crate::history::{Operation, OperationHistory};
+use crate::listing::list_entries;
+use crate::models::{
+    BatchFailure, BatchReport, ConflictSide, ConflictStrategy, CopyConflict, DeleteMode, ErrorKind,
+    FileEntry, FileProperties, ItemDetails, ItemResult, ItemState, ItemStatus, ListOptions,
+    OpError, Resolution, TransferOptions,
+};
+use crate::naming::{is_same_entry, validate_file_name};
+use crate::natural_sort::{compare_folded, fold};
+use crate::shred::shred_path;
+use crate::transfer::{
+    copy_path, generate_unique_name, is_real_dir, is_same_or_descendant, merge_dir,
+    merge_move_dir, move_path, remove_path, run_batch, PlannedItem, PlannedTransfer,
+    ProgressTracker, ScanTotals,
+};
 use std::path::Path;
+use tauri::AppHandle;
 
+/// フォルダの中身。`options` を省略するとフォルダを先に名前順で返す
 #[tauri::command]
-pub fn read_directory(path: String) -> Result<Vec<FileEntry>, String> {
-    let dir_path = Path::new(&path);
+pub fn read_directory(
+    path: String,
+    options: Option<ListOptions>,
+) -> Result<Vec<FileEntry>, String> {
+    list_entries(open_directory(&path)?, &options.unwrap_or_default())
+}
+
+pub(crate) fn open_directory(path: &str) -> Result<std::fs::ReadDir, String> {
+    let dir_path = Path::new(path);
 
     if !dir_path.exists() {
         return Err(format!("パスが存在しません: {}", path));
@@ -12,22 +36,7 @@ pub fn read_directory(path: String) -> Result<Vec<FileEntry>, String> {
         return Err(format!("ディレクトリではありません: {}", path));
     }
 
-    let mut entries: Vec<FileEntry> = std::fs::read_dir(dir_path)
-        .map_err(|e| format!("ディレクトリ読み取りエラー: {}", e))?
-        .filter_map(|entry| {
-            let entry = entry.ok()?;
-            FileEntry::from_path(&entry.path())
-        })
-        .collect();
-
-    // ディレクトリを先に、名前でソート
-    entries.sort_by(|a, b| {
-        b.is_dir.cmp(&a.is_dir).then_with(|| {
-            a.name.to_lowercase().cmp(&b.name.to_lowercase())
-        })
-    });
-
-    Ok(entries)
+    std::fs::read_dir(dir_path).map_err(|e| format!("ディレクトリ読み取りエラー: {}", e))
 }
 
 #[tauri::command]
@@ -37,42 +46,86 @@ pub fn get_home_dir() -> Result<String, String> {
         .ok_or_else(|| "ホームディレクトリが見つかりません".to_string())
 }
 
-#[tauri::command]
-pub fn copy_items(sources: Vec<String>, destination: String) -> Result<(), String> {
-    let dest_path = Path::new(&destination);
-    if !dest_path.is_dir() {
-        return Err("コピー先がディレクトリではありません".to_string());
-    }
+/// ブロッキングなファイル操作をバックグラウンドで実行する（UI フリーズ防止）
+pub(crate) async fn run_blocking<T, E, F>(f: F) -> Result<T, E>
+where
+    T: Send + 'static,
+    E: From<OpError> + Send + 'static,
+    F: FnOnce() -> Result<T, E> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(f)
+        .await
+        .map_err(|e| OpError::new(ErrorKind::Other, format!("スレッド実行失敗: {}", e)))?
+}
 
-    for source in &sources {
-        let src_path = Path::new(source);
-        let file_name = src_path
-            .file_name()
-            .ok_or_else(|| format!("ファイル名が取得できません: {}", source))?;
-        let mut dest = dest_path.join(file_name);
+/// 途中で失敗・キャンセルしても、それまでに済んだ項目は `record` で操作履歴に残す
+fn record_completed(
+    result: Result<BatchReport, BatchFailure>,
+    record: impl FnOnce(&BatchReport),
+) -> Result<BatchReport, BatchFailure> {
+    match &result {
+        Ok(report) => record(report),
+        Err(failure) => record(&failure.report),
+    }
+    result
+}
 
-        // Same-folder copy: auto-rename to avoid collision
-        let same_folder = src_path.parent() == Some(dest_path);
-        if same_folder && (dest == src_path || dest.exists()) {
-            dest = generate_unique_name(&dest);
-        }
+/// フォルダを自分自身やその配下へコピー・移動しようとした
+fn nested_destination(source: &str) -> PlannedItem {
+    PlannedItem::Invalid {
+        source: source.to_string(),
+        error: OpError::new(
+            ErrorKind::InvalidInput,
+            format!("フォルダをそれ自身の中へコピー・移動することはできません: {}", source),
+        ),
+    }
+}
 
-        if src_path.is_dir() {
-            copy_dir_recursive(src_path, &dest)?;
-        } else {
-            std::fs::copy(src_path, &dest)
-                .map_err(|e| format!("コピー失敗: {} -> {}: {}", source, dest.display(), e))?;
-        }
+/// ファイル名が取得できない操作対象（ルートなど）
+fn invalid_source(source: &str) -> PlannedItem {
+    PlannedItem::Invalid {
+        source: source.to_string(),
+        error: OpError::new(
+            ErrorKind::InvalidInput,
+            format!("ファイル名が取得できません: {}", source),
+        ),
     }
+}
 
-    Ok(())
+#[tauri::command]
+pub async fn copy_items(
+    app: AppHandle,
+    history: tauri::State<'_, OperationHistory>,
+    sources: Vec<String>,
+    destination: String,
+    options: Option<TransferOptions>,
+    operation_id: Option<String>,
+) -> Result<BatchReport, String> {
+    let history = history.inner().clone();
+    run_blocking(move || {
+        let mut tracker = ProgressTracker::for_app(&app, operation_id);
+        let result =
+            copy_items_blocking(sources, destination, options.unwrap_or_default(), &mut tracker);
+        record_completed(result, |report| history.record_copy(report)).map_err(String::from)
+    })
+    .await
+}
+
+pub(crate) fn copy_items_blocking(
+    sources: Vec<String>,
+    destination: String,
+    options: TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<BatchReport, BatchFailure> {
+    // 衝突時の既定動作は上書き
+    copy_with_strategy(sources, destination, ConflictStrategy::Overwrite, options, tracker)
 }
 
 #[tauri::command]
 pub fn check_copy_conflicts(
     sources: Vec<String>,
     destination: String,
-) -> Result<Vec<String>, String> {
+) -> Result<Vec<CopyConflict>, String> {
     let dest_path = Path::new(&destination);
     let mut conflicts = Vec::new();
     for source in &sources {
@@ -80,8 +133,19 @@ pub fn check_copy_conflicts(
         if let Some(name) = src_path.file_name() {
             let dest = dest_path.join(name);
             let same_folder = src_path.parent() == Some(dest_path);
-            if !same_folder && dest.exists() {
-                conflicts.push(name.to_string_lossy().to_string());
+            if same_folder {
+                continue;
+            }
+            if let (Some(source_info), Some(destination_info)) =
+                (ConflictSide::from_path(src_path), ConflictSide::from_path(&dest))
+            {
+                conflicts.push(CopyConflict {
+                    name: name.to_string_lossy().to_string(),
+                    source: source.clone(),
+                    destination: dest.to_string_lossy().to_string(),
+                    source_info,
+                    destination_info,
+                });
             }
         }
     }
@@ -89,155 +153,476 @@ pub fn check_copy_conflicts(
 }
 
 #[tauri::command]
-pub fn copy_items_with_strategy(
+pub async fn copy_items_with_strategy(
+    app: AppHandle,
+    history: tauri::State<'_, OperationHistory>,
     sources: Vec<String>,
     destination: String,
     strategy: String,
-) -> Result<(), String> {
+    options: Option<TransferOptions>,
+    operation_id: Option<String>,
+) -> Result<BatchReport, String> {
+    let history = history.inner().clone();
+    run_blocking(move || {
+        let mut tracker = ProgressTracker::for_app(&app, operation_id);
+        let result = copy_items_with_strategy_blocking(
+            sources,
+            destination,
+            strategy,
+            options.unwrap_or_default(),
+            &mut tracker,
+        );
+        record_completed(result, |report| history.record_copy(report)).map_err(String::from)
+    })
+    .await
+}
+
+pub(crate) fn copy_items_with_strategy_blocking(
+    sources: Vec<String>,
+    destination: String,
+    strategy: String,
+    options: TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<BatchReport, BatchFailure> {
+    let strategy = ConflictStrategy::parse(&strategy);
+    copy_with_strategy(sources, destination, strategy, options, tracker)
+}
+
+/// 衝突を項目ごとに解決してからコピーする。
+/// `options.decisions` に指定がある項目はそちらを優先する。
+/// `options.merge_directories` なら同名フォルダは統合し、中のファイルごとに解決する。
+fn copy_with_strategy(
+    sources: Vec<String>,
+    destination: String,
+    default_strategy: ConflictStrategy,
+    options: TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<BatchReport, BatchFailure> {
     let dest_path = Path::new(&destination);
     if !dest_path.is_dir() {
-        return Err("コピー先がディレクトリではありません".to_string());
+        return Err(
+            OpError::new(ErrorKind::InvalidInput, "コピー先がディレクトリではありません").into(),
+        );
     }
 
+    let mut plan = Vec::new();
     for source in &sources {
         let src_path = Path::new(source);
-        let file_name = src_path
-            .file_name()
-            .ok_or_else(|| format!("ファイル名が取得できません: {}", source))?;
+        let Some(file_name) = src_path.file_name() else {
+            plan.push(invalid_source(source));
+            continue;
+        };
         let mut dest = dest_path.join(file_name);
+        if is_real_dir(src_path) && is_same_or_descendant(src_path, dest_path) {
+            plan.push(nested_destination(source));
+            continue;
+        }
 
+        // Same-folder copy: auto-rename to avoid collision
         let same_folder = src_path.parent() == Some(dest_path);
         if same_folder && (dest == src_path || dest.exists()) {
             dest = generate_unique_name(&dest);
         } else if !same_folder && dest.exists() {
-            match strategy.as_str() {
-                "skip" => continue,
-                "rename" => dest = generate_unique_name(&dest),
-                _ => {} // "overwrite" — proceed with copy (overwrites)
+            let strategy = options
+                .decisions
+                .get(source)
+                .copied()
+                .unwrap_or(default_strategy);
+            if options.merge_directories && is_real_dir(src_path) && dest.is_dir() {
+                plan.push(PlannedItem::Transfer(
+                    PlannedTransfer::new(src_path, dest, options.symlinks).merging(strategy),
+                ));
+                continue;
+            }
+            match strategy.resolve(src_path, &dest) {
+                Resolution::Skip => {
+                    plan.push(PlannedItem::Skipped {
+                        source: source.clone(),
+                    });
+                    continue;
+                }
+                Resolution::Rename => dest = generate_unique_name(&dest),
+                Resolution::Overwrite => {} // proceed with copy (overwrites)
             }
         }
 
-        if src_path.is_dir() {
-            copy_dir_recursive(src_path, &dest)?;
-        } else {
-            std::fs::copy(src_path, &dest)
-                .map_err(|e| format!("コピー失敗: {} -> {}: {}", source, dest.display(), e))?;
-        }
+        plan.push(PlannedItem::Transfer(PlannedTransfer::new(src_path, dest, options.symlinks)));
     }
-    Ok(())
-}
-
-fn generate_unique_name(path: &Path) -> std::path::PathBuf {
-    let parent = path.parent().unwrap_or(Path::new("."));
-    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
-    let ext = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
 
-    let mut n = 2;
-    loop {
-        let candidate = parent.join(format!("{} ({}){}", stem, n, ext));
-        if !candidate.exists() {
-            return candidate;
-        }
-        n += 1;
-    }
+    copy_planned_blocking(plan, &options, tracker)
 }
 
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
-    std::fs::create_dir_all(dst)
-        .map_err(|e| format!("ディレクトリ作成失敗: {}", e))?;
-
-    for entry in std::fs::read_dir(src).map_err(|e| e.to_string())? {
-        let entry = entry.map_err(|e| e.to_string())?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
-
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            std::fs::copy(&src_path, &dst_path)
-                .map_err(|e| format!("コピー失敗: {}", e))?;
+/// 衝突解決済みのコピー計画を実行する（中断したジョブの再開でも使う）
+pub(crate) fn copy_planned_blocking(
+    plan: Vec<PlannedItem>,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<BatchReport, BatchFailure> {
+    run_batch(plan, options.continue_on_error, tracker, |item, tracker| {
+        let mut details = ItemDetails {
+            merged: item.merge.is_some(),
+            created: item.creates,
+            ..Default::default()
+        };
+        match item.merge {
+            Some(strategy) => merge_dir(
+                &item.src,
+                &item.dest,
+                strategy,
+                options,
+                tracker,
+                &mut details.conflicts,
+            )?,
+            None => copy_path(&item.src, &item.dest, options, tracker)?,
         }
-    }
-    Ok(())
+        Ok(details)
+    })
 }
 
 #[tauri::command]
-pub fn move_items(sources: Vec<String>, destination: String) -> Result<(), String> {
+pub async fn move_items(
+    app: AppHandle,
+    history: tauri::State<'_, OperationHistory>,
+    sources: Vec<String>,
+    destination: String,
+    strategy: Option<String>,
+    options: Option<TransferOptions>,
+    operation_id: Option<String>,
+) -> Result<BatchReport, String> {
+    let history = history.inner().clone();
+    run_blocking(move || {
+        let mut tracker = ProgressTracker::for_app(&app, operation_id);
+        let result = move_items_blocking(
+            sources,
+            destination,
+            strategy,
+            options.unwrap_or_default(),
+            &mut tracker,
+        );
+        record_completed(result, |report| history.record_move(report)).map_err(String::from)
+    })
+    .await
+}
+
+/// `strategy` が未指定の場合、移動先に同名の項目があれば上書きせずに失敗させる
+pub(crate) fn move_items_blocking(
+    sources: Vec<String>,
+    destination: String,
+    strategy: Option<String>,
+    options: TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<BatchReport, BatchFailure> {
     let dest_path = Path::new(&destination);
     if !dest_path.is_dir() {
-        return Err("移動先がディレクトリではありません".to_string());
+        return Err(
+            OpError::new(ErrorKind::InvalidInput, "移動先がディレクトリではありません").into(),
+        );
     }
+    let default_strategy = strategy.as_deref().map(ConflictStrategy::parse);
 
+    let mut plan = Vec::new();
     for source in &sources {
         let src_path = Path::new(source);
-        let file_name = src_path
-            .file_name()
-            .ok_or_else(|| format!("ファイル名が取得できません: {}", source))?;
-        let dest = dest_path.join(file_name);
-
-        // rename fails across drives, fall back to copy + delete
-        if std::fs::rename(src_path, &dest).is_err() {
-            if src_path.is_dir() {
-                copy_dir_recursive(src_path, &dest)?;
-                std::fs::remove_dir_all(src_path)
-                    .map_err(|e| format!("移動元の削除失敗: {}: {}", source, e))?;
-            } else {
-                std::fs::copy(src_path, &dest)
-                    .map_err(|e| format!("コピー失敗: {} -> {}: {}", source, dest.display(), e))?;
-                std::fs::remove_file(src_path)
-                    .map_err(|e| format!("移動元の削除失敗: {}: {}", source, e))?;
+        let Some(file_name) = src_path.file_name() else {
+            plan.push(invalid_source(source));
+            continue;
+        };
+        let mut dest = dest_path.join(file_name);
+
+        // 同じフォルダへの移動は何もしない
+        if dest == src_path {
+            plan.push(PlannedItem::Skipped {
+                source: source.clone(),
+            });
+            continue;
+        }
+        if is_real_dir(src_path) && is_same_or_descendant(src_path, dest_path) {
+            plan.push(nested_destination(source));
+            continue;
+        }
+
+        if dest.symlink_metadata().is_ok() {
+            let Some(strategy) = options.decisions.get(source).copied().or(default_strategy)
+            else {
+                plan.push(PlannedItem::Invalid {
+                    source: source.clone(),
+                    error: OpError::new(
+                        ErrorKind::AlreadyExists,
+                        format!("同名の項目が既に存在します: {}", dest.display()),
+                    ),
+                });
+                continue;
+            };
+            if options.merge_directories && is_real_dir(src_path) && dest.is_dir() {
+                plan.push(PlannedItem::Transfer(
+                    PlannedTransfer::new(src_path, dest, options.symlinks).merging(strategy),
+                ));
+                continue;
+            }
+            match strategy.resolve(src_path, &dest) {
+                Resolution::Skip => {
+                    plan.push(PlannedItem::Skipped {
+                        source: source.clone(),
+                    });
+                    continue;
+                }
+                Resolution::Rename => dest = generate_unique_name(&dest),
+                Resolution::Overwrite => {}
             }
         }
+
+        plan.push(PlannedItem::Transfer(PlannedTransfer::new(src_path, dest, options.symlinks)));
     }
 
-    Ok(())
+    run_batch(plan, options.continue_on_error, tracker, |item, tracker| {
+        let mut details = ItemDetails {
+            merged: item.merge.is_some(),
+            ..Default::default()
+        };
+        match item.merge {
+            Some(strategy) => merge_move_dir(
+                &item.src,
+                &item.dest,
+                strategy,
+                &options,
+                tracker,
+                &mut details.conflicts,
+            )
+            .map_err(|e| e.with_state(ItemState::PartlyMoved))?,
+            None => move_path(&item.src, &item.dest, item.totals, &options, tracker)?,
+        }
+        Ok(details)
+    })
 }
 
+/// `mode` が未指定ならゴミ箱へ移動する。
+/// 失敗はゴミ箱が使えない（`TrashUnavailable`）などの種類付きで返す
 #[tauri::command]
-pub fn delete_items(paths: Vec<String>) -> Result<(), String> {
+pub async fn delete_items(
+    history: tauri::State<'_, OperationHistory>,
+    paths: Vec<String>,
+    continue_on_error: Option<bool>,
+    mode: Option<DeleteMode>,
+) -> Result<BatchReport, OpError> {
+    let history = history.inner().clone();
+    let mode = mode.unwrap_or_default();
+    run_blocking(move || {
+        delete_and_record(&history, paths, mode, continue_on_error.unwrap_or(false))
+    })
+    .await
+}
+
+/// ゴミ箱へ移動した分は、途中で失敗しても操作履歴に記録する
+fn delete_and_record(
+    history: &OperationHistory,
+    paths: Vec<String>,
+    mode: DeleteMode,
+    continue_on_error: bool,
+) -> Result<BatchReport, OpError> {
+    let mut tracker = ProgressTracker::silent();
+    let result = delete_items_blocking(paths, mode, continue_on_error, &mut tracker);
+    record_completed(result, |report| {
+        if mode == DeleteMode::Trash {
+            history.record_trash(report);
+        }
+    })
+    .map_err(OpError::from)
+}
+
+pub(crate) fn delete_items_blocking(
+    paths: Vec<String>,
+    mode: DeleteMode,
+    continue_on_error: bool,
+    tracker: &mut ProgressTracker,
+) -> Result<BatchReport, BatchFailure> {
+    // 削除は項目単位で進捗を数える
+    let per_item = ScanTotals { bytes: 0, files: 1 };
+    for _ in &paths {
+        tracker.add_total(per_item);
+    }
+
+    let mut report = BatchReport::default();
     for path_str in &paths {
-        trash::delete(path_str)
-            .map_err(|e| format!("ゴミ箱への移動失敗: {}: {}", path_str, e))?;
+        let outcome = tracker
+            .checkpoint()
+            .and_then(|_| delete_one(Path::new(path_str), mode));
+        let (status, error) = match outcome {
+            Ok(()) => (ItemStatus::Succeeded, None),
+            Err(error) if error.kind == ErrorKind::Cancelled || !continue_on_error => {
+                return Err(BatchFailure { error, report });
+            }
+            Err(e) => (ItemStatus::Failed, Some(e)),
+        };
+        tracker.complete_without_copy(Path::new(path_str), per_item);
+        report.items.push(ItemResult {
+            source: path_str.clone(),
+            destination: None,
+            status,
+            error,
+            details: ItemDetails::default(),
+        });
+    }
+    tracker.finish();
+    Ok(report)
+}
+
+fn delete_one(path: &Path, mode: DeleteMode) -> Result<(), OpError> {
+    // ゴミ箱以外は存在しない項目を黙って成功扱いにしない
+    if mode != DeleteMode::Trash && path.symlink_metadata().is_err() {
+        return Err(OpError::new(
+            ErrorKind::NotFound,
+            format!("項目が見つかりません: {}", path.display()),
+        ));
+    }
+    match mode {
+        DeleteMode::Trash => trash::delete(path).map_err(|e| {
+            let message = format!("ゴミ箱への移動失敗: {}: {}", path.display(), e);
+            // 対象があるのに移動できなければゴミ箱が使えない（完全削除なら可能）
+            let kind = if path.symlink_metadata().is_err() {
+                ErrorKind::NotFound
+            } else {
+                ErrorKind::TrashUnavailable
+            };
+            OpError::new(kind, message)
+        }),
+        DeleteMode::Permanent => remove_path(path)
+            .map_err(|e| OpError::io(&e, format!("削除失敗: {}: {}", path.display(), e))),
+        DeleteMode::Shred => shred_path(path)
+            .map_err(|e| OpError::io(&e, format!("完全消去失敗: {}: {}", path.display(), e))),
     }
-    Ok(())
 }
 
 #[tauri::command]
-pub fn rename_item(path: String, new_name: String) -> Result<String, String> {
-    let src_path = Path::new(&path);
+pub fn rename_item(
+    history: tauri::State<'_, OperationHistory>,
+    path: String,
+    new_name: String,
+) -> Result<String, OpError> {
+    let to = rename_item_blocking(&path, &new_name)?;
+    if to != path {
+        history.record(Operation::Rename {
+            from: path,
+            to: to.clone(),
+        });
+    }
+    Ok(to)
+}
+
+/// 既存の項目は上書きしない。大文字小文字だけの変更は、大文字小文字を区別しない
+/// ファイルシステムでも反映されるよう一時的な名前を経由する
+pub(crate) fn rename_item_blocking(path: &str, new_name: &str) -> Result<String, OpError> {
+    validate_file_name(new_name)?;
+    let src_path = Path::new(path);
     let parent = src_path
         .parent()
-        .ok_or_else(|| "親ディレクトリが見つかりません".to_string())?;
-    let dest_path = parent.join(&new_name);
+        .ok_or_else(|| OpError::new(ErrorKind::InvalidInput, "親ディレクトリが見つかりません"))?;
+    let dest_path = parent.join(new_name);
+    if dest_path == src_path {
+        return Ok(path.to_string());
+    }
 
-    if dest_path.exists() {
-        return Err(format!("同名のファイルが既に存在します: {}", new_name));
+    if dest_path.symlink_metadata().is_ok() && !is_same_entry(src_path, &dest_path) {
+        return Err(OpError::new(
+            ErrorKind::AlreadyExists,
+            format!("同名のファイルが既に存在します: {}", new_name),
+        ));
     }
 
-    std::fs::rename(src_path, &dest_path)
-        .map_err(|e| format!("名前変更失敗: {}", e))?;
+    let old_name = src_path.file_name().unwrap_or_default().to_string_lossy();
+    if old_name.to_lowercase() == new_name.to_lowercase() {
+        let pair = (src_path.to_path_buf(), dest_path.clone());
+        rename_all(std::slice::from_ref(&pair))
+            .into_iter()
+            .next()
+            .unwrap_or(Ok(()))?;
+    } else {
+        rename_no_replace(src_path, &dest_path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => OpError::new(
+                ErrorKind::AlreadyExists,
+                format!("同名のファイルが既に存在します: {}", new_name),
+            ),
+            _ => OpError::io(&e, format!("名前変更失敗: {}", e)),
+        })?;
+    }
 
     Ok(dest_path.to_string_lossy().to_string())
 }
 
 #[tauri::command]
-pub fn create_directory(path: String, name: String) -> Result<String, String> {
-    let dir_path = Path::new(&path).join(&name);
+pub fn create_directory(
+    history: tauri::State<'_, OperationHistory>,
+    path: String,
+    name: String,
+) -> Result<String, OpError> {
+    let created = create_directory_blocking(&path, &name)?;
+    history.record(Operation::CreateDirectory {
+        path: created.clone(),
+    });
+    Ok(created)
+}
 
-    if dir_path.exists() {
-        return Err(format!("同名のフォルダが既に存在します: {}", name));
+pub(crate) fn create_directory_blocking(path: &str, name: &str) -> Result<String, OpError> {
+    validate_file_name(name)?;
+    let dir_path = Path::new(path).join(name);
+
+    if dir_path.symlink_metadata().is_ok() {
+        return Err(OpError::new(
+            ErrorKind::AlreadyExists,
+            format!("同名のフォルダが既に存在します: {}", name),
+        ));
     }
 
     std::fs::create_dir(&dir_path)
-        .map_err(|e| format!("フォルダ作成失敗: {}", e))?;
+        .map_err(|e| OpError::io(&e, format!("フォルダ作成失敗: {}", e)))?;
 
     Ok(dir_path.to_string_lossy().to_string())
 }
 
+/// 空のファイルを作成する
+#[tauri::command]
+pub fn create_file(
+    history: tauri::State<'_, OperationHistory>,
+    path: String,
+    name: String,
+) -> Result<String, OpError> {
+    let created = create_file_blocking(&path, &name)?;
+    history.record(Operation::CreateFile {
+        path: created.clone(),
+    });
+    Ok(created)
+}
+
+pub(crate) fn create_file_blocking(path: &str, name: &str) -> Result<String, OpError> {
+    validate_file_name(name)?;
+    let file_path = Path::new(path).join(name);
+
+    std::fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&file_path)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => OpError::new(
+                ErrorKind::AlreadyExists,
+                format!("同名のファイルが既に存在します: {}", name),
+            ),
+            _ => OpError::io(&e, format!("ファイル作成失敗: {}", e)),
+        })?;
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+/// 名前に `query` を含む項目を探し、名前の自然順で返す。
+/// 名前は Unicode の正規化形を揃えて比べ、`fold_kana` ならかなと全角・半角の違いも無視する
 #[tauri::command]
-pub fn search_files(path: String, query: String, max_results: Option<usize>) -> Result<Vec<FileEntry>, String> {
+pub fn search_files(
+    path: String,
+    query: String,
+    max_results: Option<usize>,
+    fold_kana: Option<bool>,
+) -> Result<Vec<FileEntry>, String> {
     let max = max_results.unwrap_or(200);
-    let query_lower = query.to_lowercase();
+    let fold_kana = fold_kana.unwrap_or(false);
+    let query = fold(&query, fold_kana);
 
     let mut results = Vec::new();
 
@@ -250,15 +635,20 @@ pub fn search_files(path: String, query: String, max_results: Option<usize>) ->
             break;
         }
 
-        let name = entry.file_name().to_string_lossy().to_lowercase();
-        if name.contains(&query_lower) {
+        let name = fold(&entry.file_name().to_string_lossy(), fold_kana);
+        if name.contains(&query) {
             if let Some(file_entry) = FileEntry::from_path(entry.path()) {
-                results.push(file_entry);
+                results.push((name, file_entry));
             }
         }
     }
 
-    Ok(results)
+    results.sort_by(|(a_key, a), (b_key, b)| {
+        compare_folded(a_key, b_key)
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.path.cmp(&b.path))
+    });
+    Ok(results.into_iter().map(|(_, entry)| entry).collect())
 }
 
 #[tauri::command]
@@ -285,6 +675,13 @@ pub fn read_file_preview(path: String, max_bytes: Option<usize>) -> Result<Strin
     }
 }
 
+/// プロパティダイアログ用の詳細情報
+#[tauri::command]
+pub fn get_file_properties(path: String) -> Result<FileProperties, OpError> {
+    FileProperties::from_path(Path::new(&path))
+        .map_err(|e| OpError::io(&e, format!("プロパティ取得失敗: {}", e)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,21 +700,21 @@ mod tests {
     #[test]
     fn read_directory_lists_files() {
         let dir = setup_dir();
-        let entries = read_directory(dir.path().to_string_lossy().to_string()).unwrap();
+        let entries = read_directory(dir.path().to_string_lossy().to_string(), None).unwrap();
         assert_eq!(entries.len(), 3);
     }
 
     #[test]
     fn read_directory_dirs_first() {
         let dir = setup_dir();
-        let entries = read_directory(dir.path().to_string_lossy().to_string()).unwrap();
+        let entries = read_directory(dir.path().to_string_lossy().to_string(), None).unwrap();
         assert!(entries[0].is_dir);
         assert!(!entries[1].is_dir);
     }
 
     #[test]
     fn read_directory_nonexistent_path() {
-        let result = read_directory("/nonexistent/path".to_string());
+        let result = read_directory("/nonexistent/path".to_string(), None);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("パスが存在しません"));
     }
@@ -328,7 +725,7 @@ mod tests {
         let file = dir.path().join("file.txt");
         fs::write(&file, "content").unwrap();
 
-        let result = read_directory(file.to_string_lossy().to_string());
+        let result = read_directory(file.to_string_lossy().to_string(), None);
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("ディレクトリではありません"));
     }
@@ -338,9 +735,9 @@ mod tests {
     #[test]
     fn create_directory_success() {
         let dir = tempfile::tempdir().unwrap();
-        let result = create_directory(
-            dir.path().to_string_lossy().to_string(),
-            "new_dir".to_string(),
+        let result = create_directory_blocking(
+            &dir.path().to_string_lossy(),
+            "new_dir",
         );
         assert!(result.is_ok());
         assert!(dir.path().join("new_dir").is_dir());
@@ -351,12 +748,31 @@ mod tests {
         let dir = tempfile::tempdir().unwrap();
         fs::create_dir(dir.path().join("existing")).unwrap();
 
-        let result = create_directory(
-            dir.path().to_string_lossy().to_string(),
-            "existing".to_string(),
+        let result = create_directory_blocking(
+            &dir.path().to_string_lossy(),
+            "existing",
         );
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("同名のフォルダが既に存在します"));
+        assert_eq!(result.unwrap_err().kind, ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn create_directory_rejects_invalid_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = create_directory_blocking(&dir.path().to_string_lossy(), "../escape");
+        assert_eq!(result.unwrap_err().kind, ErrorKind::PathSeparator);
+        assert!(!dir.path().parent().unwrap().join("escape").exists());
+    }
+
+    // --- create_file ---
+
+    #[test]
+    fn create_file_creates_empty_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let created = create_file_blocking(&dir.path().to_string_lossy(), "memo.txt").unwrap();
+        assert_eq!(fs::read(&created).unwrap(), b"");
+
+        let again = create_file_blocking(&dir.path().to_string_lossy(), "memo.txt");
+        assert_eq!(again.unwrap_err().kind, ErrorKind::AlreadyExists);
     }
 
     // --- rename_item ---
@@ -367,9 +783,9 @@ mod tests {
         let file = dir.path().join("old.txt");
         fs::write(&file, "content").unwrap();
 
-        let result = rename_item(
-            file.to_string_lossy().to_string(),
-            "new.txt".to_string(),
+        let result = rename_item_blocking(
+            &file.to_string_lossy(),
+            "new.txt",
         );
         assert!(result.is_ok());
         assert!(!file.exists());
@@ -383,12 +799,41 @@ mod tests {
         fs::write(&file, "a").unwrap();
         fs::write(dir.path().join("b.txt"), "b").unwrap();
 
-        let result = rename_item(
-            file.to_string_lossy().to_string(),
-            "b.txt".to_string(),
+        let result = rename_item_blocking(
+            &file.to_string_lossy(),
+            "b.txt",
         );
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("同名のファイルが既に存在します"));
+        let error = result.unwrap_err();
+        assert_eq!(error.kind, ErrorKind::AlreadyExists);
+        assert!(error.message.contains("同名のファイルが既に存在します"));
+    }
+
+    #[test]
+    fn rename_item_case_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("readme.md");
+        fs::write(&file, "content").unwrap();
+
+        let renamed = rename_item_blocking(&file.to_string_lossy(), "README.md").unwrap();
+        assert!(renamed.ends_with("README.md"));
+        let names: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, ["README.md"]);
+    }
+
+    #[test]
+    fn rename_item_rejects_invalid_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "a").unwrap();
+
+        let rename = |name: &str| rename_item_blocking(&file.to_string_lossy(), name);
+        assert_eq!(rename("../b.txt").unwrap_err().kind, ErrorKind::PathSeparator);
+        assert_eq!(rename("CON").unwrap_err().kind, ErrorKind::ReservedName);
+        assert_eq!(rename("a:b").unwrap_err().kind, ErrorKind::InvalidCharacter);
+        assert!(file.exists());
     }
 
     // --- copy_items ---
@@ -401,9 +846,11 @@ mod tests {
         let dest_dir = dir.path().join("dest");
         fs::create_dir(&dest_dir).unwrap();
 
-        let result = copy_items(
+        let result = copy_items_blocking(
             vec![src.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         assert!(dest_dir.join("src.txt").exists());
@@ -419,14 +866,127 @@ mod tests {
         let dest_dir = dir.path().join("dest");
         fs::create_dir(&dest_dir).unwrap();
 
-        let result = copy_items(
+        let result = copy_items_blocking(
             vec![src_dir.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         assert!(dest_dir.join("src_dir").join("file.txt").exists());
     }
 
+    #[test]
+    fn copy_items_continue_on_error_reports_each_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        fs::write(&src, "data").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        let missing = dir.path().join("missing.txt");
+
+        let report = copy_items_blocking(
+            vec![
+                missing.to_string_lossy().to_string(),
+                src.to_string_lossy().to_string(),
+            ],
+            dest_dir.to_string_lossy().to_string(),
+            TransferOptions {
+                continue_on_error: true,
+                ..Default::default()
+            },
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+
+        assert_eq!(report.items.len(), 2);
+        assert_eq!(report.items[0].status, ItemStatus::Failed);
+        assert_eq!(report.items[0].error.as_ref().unwrap().kind, ErrorKind::NotFound);
+        assert_eq!(report.items[1].status, ItemStatus::Succeeded);
+        assert!(dest_dir.join("src.txt").exists());
+    }
+
+    #[test]
+    fn copy_items_aborts_on_first_error_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        fs::write(&src, "data").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+
+        let result = copy_items_blocking(
+            vec![
+                dir.path().join("missing.txt").to_string_lossy().to_string(),
+                src.to_string_lossy().to_string(),
+            ],
+            dest_dir.to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        );
+        assert!(result.unwrap_err().error.message.contains("コピー失敗"));
+        assert!(!dest_dir.join("src.txt").exists());
+    }
+
+    #[test]
+    fn aborted_copy_records_items_copied_before_the_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        fs::write(&src, "data").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+
+        let result = copy_items_blocking(
+            vec![
+                src.to_string_lossy().to_string(),
+                dir.path().join("missing.txt").to_string_lossy().to_string(),
+            ],
+            dest_dir.to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        );
+        let history = OperationHistory::new();
+        let result = record_completed(result, |report| history.record_copy(report));
+
+        let failure = result.unwrap_err();
+        assert_eq!(failure.report.items.len(), 1);
+        assert_eq!(failure.report.items[0].status, ItemStatus::Succeeded);
+        let created = vec![dest_dir.join("src.txt").to_string_lossy().to_string()];
+        assert_eq!(history.list().undo[0].operation, Operation::Copy { created });
+    }
+
+    #[test]
+    fn copy_history_excludes_overwritten_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("folder")).unwrap();
+        fs::write(src.join("folder").join("a.txt"), "new").unwrap();
+        fs::write(src.join("file.txt"), "new").unwrap();
+        fs::write(src.join("fresh.txt"), "new").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir_all(dest_dir.join("folder")).unwrap();
+        fs::write(dest_dir.join("folder").join("untouched.txt"), "keep").unwrap();
+        fs::write(dest_dir.join("file.txt"), "old").unwrap();
+
+        let sources = ["folder", "file.txt", "fresh.txt"]
+            .iter()
+            .map(|name| src.join(name).to_string_lossy().to_string())
+            .collect();
+        let report = copy_items_blocking(
+            sources,
+            dest_dir.to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert!(report.items.iter().all(|i| i.status == ItemStatus::Succeeded));
+
+        // 上書きした項目を元に戻すと、コピーと関係ない既存の中身までゴミ箱へ送ってしまう
+        let history = OperationHistory::new();
+        history.record_copy(&report);
+        let created = vec![dest_dir.join("fresh.txt").to_string_lossy().to_string()];
+        assert_eq!(history.list().undo[0].operation, Operation::Copy { created });
+    }
+
     // --- move_items ---
 
     #[test]
@@ -437,9 +997,12 @@ mod tests {
         let dest_dir = dir.path().join("dest");
         fs::create_dir(&dest_dir).unwrap();
 
-        let result = move_items(
+        let result = move_items_blocking(
             vec![src.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
+            None,
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         assert!(!src.exists());
@@ -452,13 +1015,147 @@ mod tests {
         let src = dir.path().join("file.txt");
         fs::write(&src, "data").unwrap();
 
-        let result = move_items(
+        let result = move_items_blocking(
             vec![src.to_string_lossy().to_string()],
             "/nonexistent".to_string(),
+            None,
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_err());
     }
 
+    #[test]
+    fn move_items_existing_destination_fails_without_strategy() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("file.txt");
+        fs::write(&src, "new").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("file.txt"), "old").unwrap();
+
+        let result = move_items_blocking(
+            vec![src.to_string_lossy().to_string()],
+            dest_dir.to_string_lossy().to_string(),
+            None,
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        );
+        assert!(result.unwrap_err().error.message.contains("同名の項目が既に存在します"));
+        assert!(src.exists());
+        assert_eq!(fs::read_to_string(dest_dir.join("file.txt")).unwrap(), "old");
+    }
+
+    #[test]
+    fn move_items_with_rename_strategy() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("file.txt");
+        fs::write(&src, "new").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("file.txt"), "old").unwrap();
+
+        let report = move_items_blocking(
+            vec![src.to_string_lossy().to_string()],
+            dest_dir.to_string_lossy().to_string(),
+            Some("rename".to_string()),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert_eq!(report.items[0].status, ItemStatus::Succeeded);
+        assert!(!src.exists());
+        assert_eq!(fs::read_to_string(dest_dir.join("file.txt")).unwrap(), "old");
+        assert_eq!(fs::read_to_string(dest_dir.join("file (2).txt")).unwrap(), "new");
+    }
+
+    #[test]
+    fn move_items_with_skip_strategy_keeps_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("file.txt");
+        fs::write(&src, "new").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("file.txt"), "old").unwrap();
+
+        let report = move_items_blocking(
+            vec![src.to_string_lossy().to_string()],
+            dest_dir.to_string_lossy().to_string(),
+            Some("skip".to_string()),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert_eq!(report.items[0].status, ItemStatus::Skipped);
+        assert!(src.exists());
+    }
+
+    #[test]
+    fn move_items_into_own_subdirectory_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("folder");
+        let child = folder.join("child");
+        fs::create_dir_all(&child).unwrap();
+
+        let result = move_items_blocking(
+            vec![folder.to_string_lossy().to_string()],
+            child.to_string_lossy().to_string(),
+            None,
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        );
+        assert!(result.unwrap_err().error.message.contains("それ自身の中へ"));
+        assert!(child.is_dir());
+    }
+
+    #[test]
+    fn copy_items_into_itself_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let folder = dir.path().join("folder");
+        fs::create_dir(&folder).unwrap();
+        fs::write(folder.join("a.txt"), "a").unwrap();
+
+        let report = copy_items_blocking(
+            vec![folder.to_string_lossy().to_string()],
+            folder.to_string_lossy().to_string(),
+            TransferOptions {
+                continue_on_error: true,
+                ..Default::default()
+            },
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        let error = report.items[0].error.as_ref().unwrap();
+        assert_eq!(error.kind, ErrorKind::InvalidInput);
+        assert!(!folder.join("folder").exists());
+    }
+
+    #[test]
+    fn move_items_merges_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let src_dir = dir.path().join("photos");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("new.jpg"), "new").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir_all(dest_dir.join("photos")).unwrap();
+        fs::write(dest_dir.join("photos").join("old.jpg"), "old").unwrap();
+
+        move_items_blocking(
+            vec![src_dir.to_string_lossy().to_string()],
+            dest_dir.to_string_lossy().to_string(),
+            Some("overwrite".to_string()),
+            TransferOptions {
+                merge_directories: true,
+                ..Default::default()
+            },
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert!(!src_dir.exists());
+        assert!(dest_dir.join("photos").join("new.jpg").exists());
+        assert!(dest_dir.join("photos").join("old.jpg").exists());
+    }
+
     // --- copy_items: same-folder auto-rename (#52) ---
 
     #[test]
@@ -467,9 +1164,11 @@ mod tests {
         let src = dir.path().join("file.txt");
         fs::write(&src, "original").unwrap();
 
-        let result = copy_items(
+        let result = copy_items_blocking(
             vec![src.to_string_lossy().to_string()],
             dir.path().to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         // Original should still exist
@@ -487,9 +1186,11 @@ mod tests {
         fs::write(&src, "original").unwrap();
         fs::write(dir.path().join("file (2).txt"), "existing").unwrap();
 
-        let result = copy_items(
+        let result = copy_items_blocking(
             vec![src.to_string_lossy().to_string()],
             dir.path().to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         let renamed = dir.path().join("file (3).txt");
@@ -503,9 +1204,11 @@ mod tests {
         fs::create_dir(&src_dir).unwrap();
         fs::write(src_dir.join("inside.txt"), "content").unwrap();
 
-        let result = copy_items(
+        let result = copy_items_blocking(
             vec![src_dir.to_string_lossy().to_string()],
             dir.path().to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         assert!(src_dir.exists());
@@ -525,9 +1228,11 @@ mod tests {
         fs::create_dir(&dest_dir).unwrap();
         fs::write(dest_dir.join("file.txt"), "old content").unwrap();
 
-        let result = copy_items(
+        let result = copy_items_blocking(
             vec![src.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         // Default behavior: overwrite
@@ -549,7 +1254,11 @@ mod tests {
             vec![src.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
         ).unwrap();
-        assert_eq!(conflicts, vec!["file.txt"]);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].name, "file.txt");
+        assert_eq!(conflicts[0].source_info.size, 4);
+        assert_eq!(conflicts[0].destination_info.size, 3);
+        assert!(conflicts[0].destination_info.modified.is_some());
     }
 
     #[test]
@@ -576,15 +1285,152 @@ mod tests {
         fs::create_dir(&dest_dir).unwrap();
         fs::write(dest_dir.join("file.txt"), "old").unwrap();
 
-        let result = copy_items_with_strategy(
+        let result = copy_items_with_strategy_blocking(
             vec![src.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
             "skip".to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         assert_eq!(fs::read_to_string(dest_dir.join("file.txt")).unwrap(), "old");
     }
 
+    #[test]
+    fn copy_items_with_strategy_skip_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("file.txt");
+        fs::write(&src, "new").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("file.txt"), "old").unwrap();
+
+        let report = copy_items_with_strategy_blocking(
+            vec![src.to_string_lossy().to_string()],
+            dest_dir.to_string_lossy().to_string(),
+            "skip".to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert_eq!(report.items[0].status, ItemStatus::Skipped);
+    }
+
+    #[test]
+    fn copy_items_with_strategy_per_item_decisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        fs::write(&a, "new a").unwrap();
+        fs::write(&b, "new b").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("a.txt"), "old a").unwrap();
+        fs::write(dest_dir.join("b.txt"), "old b").unwrap();
+
+        let mut decisions = std::collections::HashMap::new();
+        decisions.insert(b.to_string_lossy().to_string(), ConflictStrategy::Rename);
+        let report = copy_items_with_strategy_blocking(
+            vec![a.to_string_lossy().to_string(), b.to_string_lossy().to_string()],
+            dest_dir.to_string_lossy().to_string(),
+            "skip".to_string(),
+            TransferOptions {
+                decisions,
+                ..Default::default()
+            },
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+
+        assert_eq!(report.items[0].status, ItemStatus::Skipped);
+        assert_eq!(report.items[1].status, ItemStatus::Succeeded);
+        assert_eq!(fs::read_to_string(dest_dir.join("a.txt")).unwrap(), "old a");
+        assert_eq!(fs::read_to_string(dest_dir.join("b.txt")).unwrap(), "old b");
+        assert_eq!(fs::read_to_string(dest_dir.join("b (2).txt")).unwrap(), "new b");
+    }
+
+    #[test]
+    fn copy_items_with_strategy_overwrite_if_size_differs() {
+        let dir = tempfile::tempdir().unwrap();
+        let same = dir.path().join("same.txt");
+        let diff = dir.path().join("diff.txt");
+        fs::write(&same, "1234").unwrap();
+        fs::write(&diff, "12345").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("same.txt"), "abcd").unwrap();
+        fs::write(dest_dir.join("diff.txt"), "abc").unwrap();
+
+        copy_items_with_strategy_blocking(
+            vec![
+                same.to_string_lossy().to_string(),
+                diff.to_string_lossy().to_string(),
+            ],
+            dest_dir.to_string_lossy().to_string(),
+            "overwriteIfSizeDiffers".to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(dest_dir.join("same.txt")).unwrap(), "abcd");
+        assert_eq!(fs::read_to_string(dest_dir.join("diff.txt")).unwrap(), "12345");
+    }
+
+    // --- copy_items_with_strategy: directory merge ---
+
+    #[test]
+    fn copy_items_with_strategy_merges_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let src_dir = dir.path().join("project");
+        fs::create_dir_all(src_dir.join("src")).unwrap();
+        fs::write(src_dir.join("src").join("main.rs"), "new main").unwrap();
+        fs::write(src_dir.join("README.md"), "readme").unwrap();
+        let backup = dir.path().join("backup");
+        fs::create_dir_all(backup.join("project").join("src")).unwrap();
+        fs::write(backup.join("project").join("src").join("main.rs"), "old main").unwrap();
+
+        let report = copy_items_with_strategy_blocking(
+            vec![src_dir.to_string_lossy().to_string()],
+            backup.to_string_lossy().to_string(),
+            "overwrite".to_string(),
+            TransferOptions {
+                merge_directories: true,
+                ..Default::default()
+            },
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+
+        let merged = backup.join("project");
+        assert!(!backup.join("project (2)").exists());
+        assert_eq!(fs::read_to_string(merged.join("src").join("main.rs")).unwrap(), "new main");
+        assert_eq!(fs::read_to_string(merged.join("README.md")).unwrap(), "readme");
+        let conflicts = &report.items[0].details.conflicts;
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].resolution, Resolution::Overwrite);
+        assert!(conflicts[0].path.ends_with("main.rs"));
+    }
+
+    #[test]
+    fn copy_items_with_strategy_rename_without_merge_keeps_folder_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let src_dir = dir.path().join("project");
+        fs::create_dir(&src_dir).unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir_all(dest_dir.join("project")).unwrap();
+
+        copy_items_with_strategy_blocking(
+            vec![src_dir.to_string_lossy().to_string()],
+            dest_dir.to_string_lossy().to_string(),
+            "rename".to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert!(dest_dir.join("project (2)").is_dir());
+    }
+
     #[test]
     fn copy_items_with_strategy_overwrite() {
         let dir = tempfile::tempdir().unwrap();
@@ -594,10 +1440,12 @@ mod tests {
         fs::create_dir(&dest_dir).unwrap();
         fs::write(dest_dir.join("file.txt"), "old").unwrap();
 
-        let result = copy_items_with_strategy(
+        let result = copy_items_with_strategy_blocking(
             vec![src.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
             "overwrite".to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         assert_eq!(fs::read_to_string(dest_dir.join("file.txt")).unwrap(), "new");
@@ -612,10 +1460,12 @@ mod tests {
         fs::create_dir(&dest_dir).unwrap();
         fs::write(dest_dir.join("file.txt"), "old").unwrap();
 
-        let result = copy_items_with_strategy(
+        let result = copy_items_with_strategy_blocking(
             vec![src.to_string_lossy().to_string()],
             dest_dir.to_string_lossy().to_string(),
             "rename".to_string(),
+            TransferOptions::default(),
+            &mut ProgressTracker::silent(),
         );
         assert!(result.is_ok());
         // Original untouched
@@ -634,6 +1484,7 @@ mod tests {
             dir.path().to_string_lossy().to_string(),
             "alpha".to_string(),
             None,
+            None,
         ).unwrap();
         assert!(results.iter().any(|e| e.name == "alpha.txt"));
     }
@@ -645,6 +1496,7 @@ mod tests {
             dir.path().to_string_lossy().to_string(),
             "".to_string(), // 全マッチ
             Some(2),
+            None,
         ).unwrap();
         assert!(results.len() <= 2);
     }
@@ -656,10 +1508,91 @@ mod tests {
             dir.path().to_string_lossy().to_string(),
             "ALPHA".to_string(),
             None,
+            None,
         ).unwrap();
         assert!(results.iter().any(|e| e.name == "alpha.txt"));
     }
 
+    #[test]
+    fn search_files_normalizes_and_sorts_naturally() {
+        let dir = tempfile::tempdir().unwrap();
+        // NFD の「が」（か + 濁点）
+        fs::write(dir.path().join("か\u{3099}イド10.txt"), "").unwrap();
+        fs::write(dir.path().join("がいど2.txt"), "").unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+
+        let results = search_files(path.clone(), "が".to_string(), None, None).unwrap();
+        let names: Vec<_> = results.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["がいど2.txt", "か\u{3099}イド10.txt"]);
+
+        let results = search_files(path.clone(), "ガイド".to_string(), None, None).unwrap();
+        assert!(results.is_empty());
+        let results = search_files(path, "ｶﾞｲﾄﾞ".to_string(), None, Some(true)).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].name, "がいど2.txt");
+    }
+
+    // --- delete_items ---
+
+    #[test]
+    fn delete_permanently_reports_each_item() {
+        let dir = setup_dir();
+        let folder = dir.path().join("gamma_dir");
+        let file = dir.path().join("alpha.txt");
+        let missing = dir.path().join("missing.txt");
+        let paths = [&folder, &file, &missing]
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+
+        let report = delete_items_blocking(
+            paths,
+            DeleteMode::Permanent,
+            true,
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert!(!folder.exists() && !file.exists());
+        let statuses: Vec<_> = report.items.iter().map(|i| i.status).collect();
+        assert_eq!(
+            statuses,
+            [ItemStatus::Succeeded, ItemStatus::Succeeded, ItemStatus::Failed]
+        );
+        assert_eq!(report.items[2].error.as_ref().unwrap().kind, ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn delete_error_keeps_its_kind() {
+        let dir = setup_dir();
+        let file = dir.path().join("alpha.txt");
+        let missing = dir.path().join("missing.txt");
+        let paths = [&file, &missing]
+            .iter()
+            .map(|p| p.to_string_lossy().to_string())
+            .collect();
+
+        // 途中で止まっても、フロントエンドには文字列ではなく種類付きのエラーが届く
+        let history = OperationHistory::new();
+        let error = delete_and_record(&history, paths, DeleteMode::Permanent, false).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::NotFound);
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn delete_with_shred_removes_items() {
+        let dir = setup_dir();
+        let folder = dir.path().join("gamma_dir");
+        let report = delete_items_blocking(
+            vec![folder.to_string_lossy().to_string()],
+            DeleteMode::Shred,
+            false,
+            &mut ProgressTracker::silent(),
+        )
+        .unwrap();
+        assert_eq!(report.items[0].status, ItemStatus::Succeeded);
+        assert!(!folder.exists());
+    }
+
     // --- read_file_preview ---
 
     #[test]
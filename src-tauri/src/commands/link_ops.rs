@@ -0,0 +1,222 @@
+use crate::history::{Operation, OperationHistory};
+use crate::models::{ErrorKind, FileEntry, OpError};
+use crate::naming::validate_file_name;
+use crate::transfer::generate_unique_name;
+use std::path::{Component, Path, PathBuf};
+
+/// `destination` に `target` へのシンボリックリンクを作る。
+/// `relative` なら作成先フォルダからの相対パスでリンクする
+#[tauri::command]
+pub fn create_symlink(
+    history: tauri::State<'_, OperationHistory>,
+    target: String,
+    destination: String,
+    name: Option<String>,
+    relative: Option<bool>,
+) -> Result<FileEntry, OpError> {
+    let entry = create_symlink_blocking(
+        &target,
+        &destination,
+        name.as_deref(),
+        relative.unwrap_or(false),
+    )?;
+    history.record(Operation::CreateFile {
+        path: entry.path.clone(),
+    });
+    Ok(entry)
+}
+
+/// `destination` に `target` のハードリンクを作る（ファイルのみ、同じボリューム内のみ）
+#[tauri::command]
+pub fn create_hardlink(
+    history: tauri::State<'_, OperationHistory>,
+    target: String,
+    destination: String,
+    name: Option<String>,
+) -> Result<FileEntry, OpError> {
+    let entry = create_hardlink_blocking(&target, &destination, name.as_deref())?;
+    history.record(Operation::CreateFile {
+        path: entry.path.clone(),
+    });
+    Ok(entry)
+}
+
+pub(crate) fn create_symlink_blocking(
+    target: &str,
+    destination: &str,
+    name: Option<&str>,
+    relative: bool,
+) -> Result<FileEntry, OpError> {
+    let target = Path::new(target);
+    if let Err(e) = target.symlink_metadata() {
+        return Err(OpError::io(&e, format!("リンク先が見つかりません: {}", e)));
+    }
+    let link = link_path(target, destination, name)?;
+
+    let link_target = if relative {
+        relative_path(Path::new(destination), target)
+    } else {
+        target.to_path_buf()
+    };
+    symlink(&link_target, &link, target.is_dir())
+        .map_err(|e| link_error(&e, "シンボリックリンク"))?;
+    entry_for(&link)
+}
+
+pub(crate) fn create_hardlink_blocking(
+    target: &str,
+    destination: &str,
+    name: Option<&str>,
+) -> Result<FileEntry, OpError> {
+    let target = Path::new(target);
+    let target_meta = target
+        .symlink_metadata()
+        .map_err(|e| OpError::io(&e, format!("リンク元が見つかりません: {}", e)))?;
+    if target_meta.is_dir() {
+        return Err(OpError::new(
+            ErrorKind::Unsupported,
+            "フォルダにはハードリンクを作成できません",
+        ));
+    }
+    let link = link_path(target, destination, name)?;
+
+    std::fs::hard_link(target, &link).map_err(|e| link_error(&e, "ハードリンク"))?;
+    entry_for(&link)
+}
+
+/// 作成するリンクのパス。名前を省略するとリンク先と同じ名前にし、既にあれば番号を付ける
+fn link_path(target: &Path, destination: &str, name: Option<&str>) -> Result<PathBuf, OpError> {
+    let target_name = target.file_name().unwrap_or_default().to_string_lossy();
+    let name = name.unwrap_or(&target_name);
+    validate_file_name(name)?;
+
+    let link = Path::new(destination).join(name);
+    Ok(if link.symlink_metadata().is_ok() {
+        generate_unique_name(&link)
+    } else {
+        link
+    })
+}
+
+fn entry_for(link: &Path) -> Result<FileEntry, OpError> {
+    FileEntry::from_path(link).ok_or_else(|| {
+        OpError::new(
+            ErrorKind::NotFound,
+            format!("作成したリンクを読み取れません: {}", link.display()),
+        )
+    })
+}
+
+/// ボリュームをまたぐハードリンクや、リンクに対応しないファイルシステムを区別して報告する
+fn link_error(e: &std::io::Error, what: &str) -> OpError {
+    match ErrorKind::from(e.kind()) {
+        ErrorKind::CrossDevice => OpError::new(
+            ErrorKind::CrossDevice,
+            format!("別のボリュームには{}を作成できません", what),
+        ),
+        ErrorKind::Unsupported => OpError::new(
+            ErrorKind::Unsupported,
+            format!("このファイルシステムは{}に対応していません", what),
+        ),
+        _ => OpError::io(e, format!("{}の作成失敗: {}", what, e)),
+    }
+}
+
+/// `from_dir` から見た `to` の相対パス（どちらも絶対パスを想定）
+fn relative_path(from_dir: &Path, to: &Path) -> PathBuf {
+    let from: Vec<Component> = from_dir.components().collect();
+    let to_components: Vec<Component> = to.components().collect();
+    let common = from
+        .iter()
+        .zip(&to_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    // ルートやドライブが異なれば相対パスにできない
+    if common == 0 {
+        return to.to_path_buf();
+    }
+    let mut rel = PathBuf::new();
+    for _ in common..from.len() {
+        rel.push("..");
+    }
+    for component in &to_components[common..] {
+        rel.push(component);
+    }
+    rel
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path, _is_dir: bool) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path, is_dir: bool) -> std::io::Result<()> {
+    if is_dir {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn relative_path_walks_up_to_common_ancestor() {
+        assert_eq!(
+            relative_path(Path::new("/a/b/c"), Path::new("/a/d/e.txt")),
+            Path::new("../../d/e.txt")
+        );
+        assert_eq!(
+            relative_path(Path::new("/a"), Path::new("/a/b.txt")),
+            Path::new("b.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_created_relative_or_absolute() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("src").join("data.txt");
+        fs::create_dir(dir.path().join("src")).unwrap();
+        fs::write(&target, "data").unwrap();
+        let dest = dir.path().join("links");
+        fs::create_dir(&dest).unwrap();
+        let dest_str = dest.to_string_lossy();
+
+        let rel =
+            create_symlink_blocking(&target.to_string_lossy(), &dest_str, None, true).unwrap();
+        assert!(rel.is_symlink);
+        assert_eq!(
+            fs::read_link(&rel.path).unwrap(),
+            Path::new("../src/data.txt")
+        );
+        assert_eq!(fs::read_to_string(&rel.path).unwrap(), "data");
+
+        let abs =
+            create_symlink_blocking(&target.to_string_lossy(), &dest_str, None, false).unwrap();
+        assert_eq!(Path::new(&abs.path), dest.join("data (2).txt"));
+        assert_eq!(fs::read_link(&abs.path).unwrap(), target);
+    }
+
+    #[test]
+    fn hardlinks_share_content_and_reject_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("a.txt");
+        fs::write(&target, "one").unwrap();
+        let dest_str = dir.path().to_string_lossy();
+
+        let link =
+            create_hardlink_blocking(&target.to_string_lossy(), &dest_str, Some("b.txt")).unwrap();
+        assert!(!link.is_symlink);
+        fs::write(&target, "two").unwrap();
+        assert_eq!(fs::read_to_string(&link.path).unwrap(), "two");
+
+        let err = create_hardlink_blocking(&dest_str, &dest_str, Some("dir")).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Unsupported);
+    }
+}
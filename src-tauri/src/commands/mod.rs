@@ -1,7 +1,19 @@
 pub mod clipboard;
+pub mod dir_stream;
 pub mod fs_ops;
+pub mod link_ops;
+pub mod permission_ops;
+pub mod rename_ops;
+pub mod templates;
+pub mod trash_ops;
 pub mod updater;
 
 pub use clipboard::*;
+pub use dir_stream::*;
 pub use fs_ops::*;
+pub use link_ops::*;
+pub use permission_ops::*;
+pub use rename_ops::*;
+pub use templates::*;
+pub use trash_ops::*;
 pub use updater::*;
@@ -0,0 +1,347 @@
+use super::fs_ops::run_blocking;
+use crate::models::{
+    BatchReport, ErrorKind, ItemDetails, ItemResult, ItemStatus, OpError, PermissionRules,
+};
+use crate::permissions::ModeSpec;
+use std::fs::Metadata;
+use std::path::Path;
+
+/// パーミッションを変更する。`recursive` ならフォルダの中身も対象にし、
+/// 変更した（またはしなかった）項目ごとに結果を返す
+#[tauri::command]
+pub async fn set_permissions(
+    paths: Vec<String>,
+    rules: PermissionRules,
+    continue_on_error: Option<bool>,
+) -> Result<BatchReport, String> {
+    run_blocking(move || {
+        set_permissions_blocking(&paths, &rules, continue_on_error.unwrap_or(false))
+    })
+    .await
+}
+
+/// 所有者・グループを変更する。名前か数値 ID で指定し、省略した方は変更しない
+#[tauri::command]
+pub async fn set_owner(
+    paths: Vec<String>,
+    owner: Option<String>,
+    group: Option<String>,
+    recursive: Option<bool>,
+    continue_on_error: Option<bool>,
+) -> Result<BatchReport, String> {
+    run_blocking(move || {
+        set_owner_blocking(
+            &paths,
+            owner.as_deref(),
+            group.as_deref(),
+            recursive.unwrap_or(false),
+            continue_on_error.unwrap_or(false),
+        )
+    })
+    .await
+}
+
+pub(crate) fn set_permissions_blocking(
+    paths: &[String],
+    rules: &PermissionRules,
+    continue_on_error: bool,
+) -> Result<BatchReport, String> {
+    let parse = |spec: Option<&String>| spec.map(|s| ModeSpec::parse(s)).transpose();
+    let file_spec = parse(rules.file_mode.as_ref().or(rules.mode.as_ref()))?;
+    let dir_spec = parse(rules.dir_mode.as_ref().or(rules.mode.as_ref()))?;
+    if file_spec.is_none() && dir_spec.is_none() {
+        return Err("パーミッションを指定してください".to_string());
+    }
+
+    for_each_item(
+        paths,
+        rules.recursive,
+        continue_on_error,
+        |path, metadata| {
+            // シンボリックリンクへの chmod はリンク先を変えてしまう
+            if metadata.is_symlink() {
+                return Ok(ItemStatus::Skipped);
+            }
+            let spec = if metadata.is_dir() {
+                &dir_spec
+            } else {
+                &file_spec
+            };
+            match spec {
+                Some(spec) => chmod(path, metadata, spec).map(|_| ItemStatus::Succeeded),
+                None => Ok(ItemStatus::Skipped),
+            }
+        },
+    )
+}
+
+pub(crate) fn set_owner_blocking(
+    paths: &[String],
+    owner: Option<&str>,
+    group: Option<&str>,
+    recursive: bool,
+    continue_on_error: bool,
+) -> Result<BatchReport, String> {
+    let owner = owner.filter(|o| !o.is_empty());
+    let group = group.filter(|g| !g.is_empty());
+    if owner.is_none() && group.is_none() {
+        return Err("所有者かグループを指定してください".to_string());
+    }
+    let ids = resolve_ids(owner, group)?;
+
+    for_each_item(paths, recursive, continue_on_error, |path, _| {
+        chown(path, ids).map(|_| ItemStatus::Succeeded)
+    })
+}
+
+/// 各パス（`recursive` なら配下も）に `op` を適用する。
+/// 中身を先に処理するので、フォルダの権限を絞っても配下を辿れる
+fn for_each_item<F>(
+    paths: &[String],
+    recursive: bool,
+    continue_on_error: bool,
+    mut op: F,
+) -> Result<BatchReport, String>
+where
+    F: FnMut(&Path, &Metadata) -> Result<ItemStatus, OpError>,
+{
+    let mut report = BatchReport::default();
+    for path_str in paths {
+        let root = Path::new(path_str);
+        let walker = walkdir::WalkDir::new(root)
+            .follow_links(false)
+            .contents_first(true)
+            .max_depth(if recursive { usize::MAX } else { 0 });
+
+        for entry in walker {
+            let (source, outcome) = match entry {
+                Ok(entry) => {
+                    let outcome = entry
+                        .metadata()
+                        .map_err(|e| walk_error(e, entry.path()))
+                        .and_then(|metadata| op(entry.path(), &metadata));
+                    (entry.path().to_path_buf(), outcome)
+                }
+                Err(e) => {
+                    let path = e.path().unwrap_or(root).to_path_buf();
+                    let error = walk_error(e, &path);
+                    (path, Err(error))
+                }
+            };
+            let (status, error) = match outcome {
+                Ok(status) => (status, None),
+                Err(e) => {
+                    if !continue_on_error {
+                        return Err(e.message);
+                    }
+                    (ItemStatus::Failed, Some(e))
+                }
+            };
+            report.items.push(ItemResult {
+                source: source.to_string_lossy().to_string(),
+                destination: None,
+                status,
+                error,
+                details: ItemDetails::default(),
+            });
+        }
+    }
+    Ok(report)
+}
+
+fn walk_error(e: walkdir::Error, path: &Path) -> OpError {
+    match e.into_io_error() {
+        Some(e) => OpError::io(&e, format!("{}: {}", path.display(), e)),
+        None => OpError::new(
+            ErrorKind::Other,
+            format!("フォルダを辿れません: {}", path.display()),
+        ),
+    }
+}
+
+/// 権限不足は、何が必要かを添えて報告する
+fn permission_error(e: &std::io::Error, path: &Path, hint: &str) -> OpError {
+    if e.kind() == std::io::ErrorKind::PermissionDenied {
+        OpError::new(
+            ErrorKind::PermissionDenied,
+            format!("権限がありません（{}）: {}", hint, path.display()),
+        )
+    } else {
+        OpError::io(e, format!("{}: {}", path.display(), e))
+    }
+}
+
+#[cfg(unix)]
+fn chmod(path: &Path, metadata: &Metadata, spec: &ModeSpec) -> Result<(), OpError> {
+    use std::os::unix::fs::PermissionsExt;
+    let current = metadata.permissions().mode() & 0o7777;
+    let mode = spec.apply(current, metadata.is_dir());
+    if mode == current {
+        return Ok(());
+    }
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+        .map_err(|e| permission_error(&e, path, "所有者か管理者のみ変更できます"))
+}
+
+#[cfg(not(unix))]
+fn chmod(_path: &Path, _metadata: &Metadata, _spec: &ModeSpec) -> Result<(), OpError> {
+    Err(OpError::new(
+        ErrorKind::Unsupported,
+        "この OS ではパーミッションを変更できません",
+    ))
+}
+
+#[cfg(unix)]
+type OwnerIds = (Option<u32>, Option<u32>);
+#[cfg(not(unix))]
+type OwnerIds = ();
+
+#[cfg(unix)]
+fn resolve_ids(owner: Option<&str>, group: Option<&str>) -> Result<OwnerIds, OpError> {
+    let uid = owner
+        .map(|name| {
+            crate::users::user_id(name).ok_or_else(|| {
+                OpError::new(
+                    ErrorKind::InvalidInput,
+                    format!("ユーザーが見つかりません: {}", name),
+                )
+            })
+        })
+        .transpose()?;
+    let gid = group
+        .map(|name| {
+            crate::users::group_id(name).ok_or_else(|| {
+                OpError::new(
+                    ErrorKind::InvalidInput,
+                    format!("グループが見つかりません: {}", name),
+                )
+            })
+        })
+        .transpose()?;
+    Ok((uid, gid))
+}
+
+#[cfg(not(unix))]
+fn resolve_ids(_owner: Option<&str>, _group: Option<&str>) -> Result<OwnerIds, OpError> {
+    Err(OpError::new(
+        ErrorKind::Unsupported,
+        "この OS では所有者を変更できません",
+    ))
+}
+
+#[cfg(unix)]
+fn chown(path: &Path, (uid, gid): OwnerIds) -> Result<(), OpError> {
+    // シンボリックリンクはリンク自身の所有者を変える
+    std::os::unix::fs::lchown(path, uid, gid)
+        .map_err(|e| permission_error(&e, path, "所有者の変更には管理者権限が必要です"))
+}
+
+#[cfg(not(unix))]
+fn chown(_path: &Path, _ids: OwnerIds) -> Result<(), OpError> {
+    Err(OpError::new(
+        ErrorKind::Unsupported,
+        "この OS では所有者を変更できません",
+    ))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn mode_of(path: &Path) -> u32 {
+        fs::symlink_metadata(path).unwrap().permissions().mode() & 0o7777
+    }
+
+    fn rules(mode: Option<&str>, file: Option<&str>, dir: Option<&str>) -> PermissionRules {
+        PermissionRules {
+            mode: mode.map(String::from),
+            file_mode: file.map(String::from),
+            dir_mode: dir.map(String::from),
+            recursive: true,
+        }
+    }
+
+    #[test]
+    fn applies_separate_file_and_dir_rules_recursively() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("tree");
+        fs::create_dir_all(root.join("sub")).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+        fs::write(root.join("sub").join("b.txt"), "b").unwrap();
+        std::os::unix::fs::symlink("a.txt", root.join("link")).unwrap();
+
+        let paths = vec![root.to_string_lossy().to_string()];
+        let report =
+            set_permissions_blocking(&paths, &rules(None, Some("640"), Some("750")), false)
+                .unwrap();
+
+        assert_eq!(mode_of(&root), 0o750);
+        assert_eq!(mode_of(&root.join("sub")), 0o750);
+        assert_eq!(mode_of(&root.join("a.txt")), 0o640);
+        assert_eq!(mode_of(&root.join("sub").join("b.txt")), 0o640);
+        assert_eq!(report.items.len(), 5);
+        let link = report
+            .items
+            .iter()
+            .find(|i| i.source.ends_with("link"))
+            .unwrap();
+        assert_eq!(link.status, ItemStatus::Skipped);
+    }
+
+    #[test]
+    fn symbolic_mode_without_recursion_touches_only_the_item() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("run.sh");
+        fs::write(&file, "").unwrap();
+        fs::set_permissions(&file, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let mut rules = rules(Some("u+x,go-r"), None, None);
+        rules.recursive = false;
+        let paths = vec![file.to_string_lossy().to_string()];
+        let report = set_permissions_blocking(&paths, &rules, false).unwrap();
+        assert_eq!(mode_of(&file), 0o700);
+        assert_eq!(report.items.len(), 1);
+        assert_eq!(report.items[0].status, ItemStatus::Succeeded);
+    }
+
+    #[test]
+    fn reports_invalid_specs_and_missing_items() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = vec![dir.path().to_string_lossy().to_string()];
+        assert!(set_permissions_blocking(&paths, &rules(Some("9z"), None, None), false).is_err());
+        assert!(set_permissions_blocking(&paths, &rules(None, None, None), false).is_err());
+
+        let missing = vec![dir.path().join("missing").to_string_lossy().to_string()];
+        let report =
+            set_permissions_blocking(&missing, &rules(Some("644"), None, None), true).unwrap();
+        assert_eq!(report.items[0].status, ItemStatus::Failed);
+        assert_eq!(
+            report.items[0].error.as_ref().unwrap().kind,
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn set_owner_accepts_current_ids_and_rejects_unknown_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "").unwrap();
+        let paths = vec![file.to_string_lossy().to_string()];
+        let uid = unsafe { libc::getuid() }.to_string();
+        let gid = unsafe { libc::getgid() }.to_string();
+
+        let report = set_owner_blocking(&paths, Some(&uid), Some(&gid), false, false).unwrap();
+        assert_eq!(report.items[0].status, ItemStatus::Succeeded);
+        assert!(set_owner_blocking(&paths, Some("no-such-user-xyz"), None, false, false).is_err());
+        assert!(set_owner_blocking(&paths, None, None, false, false).is_err());
+
+        // 一般ユーザーは他人に所有者を変えられない
+        if uid != "0" {
+            let report = set_owner_blocking(&paths, Some("0"), None, false, true).unwrap();
+            let error = report.items[0].error.as_ref().unwrap();
+            assert_eq!(error.kind, ErrorKind::PermissionDenied);
+        }
+    }
+}
@@ -0,0 +1,658 @@
+use super::fs_ops::run_blocking;
+use crate::history::{MovedItem, Operation, OperationHistory};
+use crate::models::{
+    BatchReport, CaseConversion, ErrorKind, FindReplace, ItemDetails, ItemResult, ItemStatus,
+    OpError, RenameRules,
+};
+use crate::naming::{is_same_entry, validate_file_name};
+use crate::transfer::generate_unique_name;
+use chrono::format::{Item, StrftimeItems};
+use regex::{Regex, RegexBuilder};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// 連番のゼロ埋めの上限（巨大な名前を作らない）
+const MAX_PADDING: usize = 32;
+
+/// 複数の項目をルールに従って名前変更する。`preview` なら変更せずに、
+/// 新しいパス（`destination`）と衝突（`Failed`）だけを返す
+#[tauri::command]
+pub async fn batch_rename(
+    history: tauri::State<'_, OperationHistory>,
+    paths: Vec<String>,
+    rules: RenameRules,
+    preview: Option<bool>,
+    continue_on_error: Option<bool>,
+) -> Result<BatchReport, String> {
+    let history = history.inner().clone();
+    run_blocking(move || {
+        let plan = plan_batch_rename(&paths, &rules)?;
+        if preview.unwrap_or(false) {
+            return Ok(preview_report(plan));
+        }
+        let report = apply_batch_rename(plan, continue_on_error.unwrap_or(false))?;
+        let items: Vec<MovedItem> = report
+            .items
+            .iter()
+            .filter(|item| item.status == ItemStatus::Succeeded)
+            .filter_map(|item| {
+                Some(MovedItem {
+                    from: item.source.clone(),
+                    to: item.destination.clone()?,
+                })
+            })
+            .collect();
+        if !items.is_empty() {
+            history.record(Operation::BatchRename { items });
+        }
+        Ok(report)
+    })
+    .await
+}
+
+/// 1 項目分の名前変更の予定
+#[derive(Debug)]
+pub(crate) struct PlannedRename {
+    src: PathBuf,
+    dest: PathBuf,
+    error: Option<OpError>,
+}
+
+impl PlannedRename {
+    fn unchanged(&self) -> bool {
+        self.src == self.dest
+    }
+}
+
+/// 新しい名前を決め、重複・既存の項目との衝突を調べる
+pub(crate) fn plan_batch_rename(
+    paths: &[String],
+    rules: &RenameRules,
+) -> Result<Vec<PlannedRename>, String> {
+    if rules.counter.padding > MAX_PADDING {
+        return Err(format!("ゼロ埋めの桁数は {} 以下にしてください", MAX_PADDING));
+    }
+    let find = rules.find_replace.as_ref().map(compile_find).transpose()?;
+    let mut plan = Vec::new();
+    for (index, path) in paths.iter().enumerate() {
+        let src = PathBuf::from(path);
+        // 桁あふれした項目は `{n}` を使う場合だけエラーにする
+        let counter = rules
+            .counter
+            .step
+            .checked_mul(index as u64)
+            .and_then(|n| n.checked_add(rules.counter.start));
+        let (dest, error) = match new_name(&src, rules, find.as_ref(), counter) {
+            Ok(name) => (src.with_file_name(name), None),
+            Err(e) => (src.clone(), Some(e)),
+        };
+        plan.push(PlannedRename { src, dest, error });
+    }
+
+    // 名前が変わる項目の元の場所は空くので、そこへの変更は衝突にならない
+    let vacated: HashSet<PathBuf> = plan
+        .iter()
+        .filter(|p| p.error.is_none() && !p.unchanged())
+        .map(|p| p.src.clone())
+        .collect();
+    let mut targets: HashMap<PathBuf, usize> = HashMap::new();
+    for p in plan.iter().filter(|p| p.error.is_none()) {
+        *targets.entry(p.dest.clone()).or_default() += 1;
+    }
+    for p in plan.iter_mut().filter(|p| p.error.is_none() && !p.unchanged()) {
+        if targets.get(&p.dest).copied().unwrap_or(0) > 1 {
+            p.error = Some(OpError::new(
+                ErrorKind::AlreadyExists,
+                format!("変更後の名前が重複しています: {}", p.dest.display()),
+            ));
+        } else if p.dest.symlink_metadata().is_ok()
+            && !vacated.contains(&p.dest)
+            && !is_same_entry(&p.src, &p.dest)
+        {
+            p.error = Some(OpError::new(
+                ErrorKind::AlreadyExists,
+                format!("同名の項目が既に存在します: {}", p.dest.display()),
+            ));
+        }
+    }
+    Ok(plan)
+}
+
+fn compile_find(rule: &FindReplace) -> Result<Regex, String> {
+    let pattern = if rule.regex {
+        rule.find.clone()
+    } else {
+        regex::escape(&rule.find)
+    };
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!rule.case_sensitive)
+        .build()
+        .map_err(|e| format!("正規表現が不正です: {}", e))
+}
+
+/// ルールを適用した新しいファイル名
+fn new_name(
+    src: &Path,
+    rules: &RenameRules,
+    find: Option<&Regex>,
+    counter: Option<u64>,
+) -> Result<String, OpError> {
+    let invalid = |message: String| OpError::new(ErrorKind::InvalidInput, message);
+    let name = src
+        .file_name()
+        .ok_or_else(|| invalid(format!("ファイル名が取得できません: {}", src.display())))?
+        .to_string_lossy()
+        .to_string();
+    // フォルダと拡張子のないファイルは名前全体を対象にする
+    let (stem, ext) = match name.rfind('.') {
+        Some(dot) if dot > 0 && !src.is_dir() => (&name[..dot], Some(&name[dot + 1..])),
+        _ => (name.as_str(), None),
+    };
+
+    let tokens = |text: &str| expand_tokens(text, src, rules, counter).map_err(invalid);
+    let mut stem = stem.to_string();
+    if let (Some(regex), Some(rule)) = (find, &rules.find_replace) {
+        let replace = tokens(&rule.replace)?;
+        let replace = if rule.regex {
+            replace
+        } else {
+            // リテラル置換では `$` を文字として扱う
+            replace.replace('$', "$$")
+        };
+        stem = regex.replace_all(&stem, replace.as_str()).to_string();
+    }
+    stem = format!("{}{}{}", tokens(&rules.prefix)?, stem, tokens(&rules.suffix)?);
+    if let Some(case) = rules.case {
+        stem = convert_case(&stem, case);
+    }
+
+    let ext = match &rules.extension {
+        Some(new_ext) => Some(new_ext.trim_start_matches('.')).filter(|e| !e.is_empty()),
+        None => ext,
+    };
+    let name = match ext {
+        Some(ext) => format!("{}.{}", stem, ext),
+        None => stem,
+    };
+    validate_file_name(&name)?;
+    Ok(name)
+}
+
+/// `{n}`・`{date}`・`{date:書式}` を展開する
+fn expand_tokens(
+    text: &str,
+    src: &Path,
+    rules: &RenameRules,
+    counter: Option<u64>,
+) -> Result<String, String> {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}').map(|i| open + i) else {
+            out.push_str(&rest[open..]);
+            return Ok(out);
+        };
+        let token = &rest[open + 1..close];
+        if token == "n" {
+            let counter = counter.ok_or_else(|| "連番が上限を超えました".to_string())?;
+            out.push_str(&format!("{:0width$}", counter, width = rules.counter.padding));
+        } else if token == "date" || token.starts_with("date:") {
+            let format = token.strip_prefix("date:").unwrap_or(DEFAULT_DATE_FORMAT);
+            out.push_str(&format_mtime(src, format)?);
+        } else {
+            // 未知のトークンはそのまま残す
+            out.push_str(&rest[open..=close]);
+        }
+        rest = &rest[close + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn format_mtime(src: &Path, format: &str) -> Result<String, String> {
+    // 不正な書式を Display に渡すと panic するので先に検証する
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        return Err(format!("日付の書式が不正です: {}", format));
+    }
+    let modified = src
+        .metadata()
+        .and_then(|m| m.modified())
+        .map_err(|e| format!("更新日時が取得できません: {}: {}", src.display(), e))?;
+    let datetime: chrono::DateTime<chrono::Local> = modified.into();
+    Ok(datetime.format(format).to_string())
+}
+
+fn convert_case(text: &str, case: CaseConversion) -> String {
+    match case {
+        CaseConversion::Lower => text.to_lowercase(),
+        CaseConversion::Upper => text.to_uppercase(),
+        CaseConversion::Title => {
+            let mut out = String::with_capacity(text.len());
+            let mut word_start = true;
+            for c in text.chars() {
+                if word_start {
+                    out.extend(c.to_uppercase());
+                } else {
+                    out.extend(c.to_lowercase());
+                }
+                word_start = !c.is_alphanumeric();
+            }
+            out
+        }
+    }
+}
+
+fn preview_report(plan: Vec<PlannedRename>) -> BatchReport {
+    let items = plan
+        .into_iter()
+        .map(|p| {
+            let status = match &p.error {
+                Some(_) => ItemStatus::Failed,
+                None if p.unchanged() => ItemStatus::Skipped,
+                None => ItemStatus::Succeeded,
+            };
+            result(&p.src, Some(&p.dest), status, p.error)
+        })
+        .collect();
+    BatchReport { items }
+}
+
+fn result(
+    src: &Path,
+    dest: Option<&Path>,
+    status: ItemStatus,
+    error: Option<OpError>,
+) -> ItemResult {
+    ItemResult {
+        source: src.to_string_lossy().to_string(),
+        destination: dest.map(|d| d.to_string_lossy().to_string()),
+        status,
+        error,
+        details: ItemDetails::default(),
+    }
+}
+
+/// 予定どおりに名前を変更する。衝突がある場合、`continue_on_error` でなければ何も変更しない。
+/// 変更を始めた後の失敗は中断せず、その項目を `Failed` にして結果を返す
+pub(crate) fn apply_batch_rename(
+    plan: Vec<PlannedRename>,
+    continue_on_error: bool,
+) -> Result<BatchReport, String> {
+    if !continue_on_error {
+        if let Some(error) = plan.iter().find_map(|p| p.error.as_ref()) {
+            return Err(error.message.clone());
+        }
+    }
+
+    let (renames, others): (Vec<_>, Vec<_>) = plan
+        .into_iter()
+        .partition(|p| p.error.is_none() && !p.unchanged());
+    let pairs: Vec<(PathBuf, PathBuf)> = renames.into_iter().map(|p| (p.src, p.dest)).collect();
+    let outcomes = rename_all(&pairs);
+
+    let mut report = BatchReport::default();
+    for ((src, dest), outcome) in pairs.iter().zip(outcomes) {
+        report.items.push(match outcome {
+            Ok(()) => result(src, Some(dest), ItemStatus::Succeeded, None),
+            Err(e) => result(src, Some(dest), ItemStatus::Failed, Some(e)),
+        });
+    }
+    for p in others {
+        let status = if p.error.is_some() {
+            ItemStatus::Failed
+        } else {
+            ItemStatus::Skipped
+        };
+        report.items.push(result(&p.src, None, status, p.error));
+    }
+    Ok(report)
+}
+
+/// 入れ替え・循環する名前変更でも上書きしないよう、いったん一時的な名前にしてから変更する
+pub(crate) fn rename_all(pairs: &[(PathBuf, PathBuf)]) -> Vec<Result<(), OpError>> {
+    let mut outcomes: Vec<Result<(), OpError>> = Vec::with_capacity(pairs.len());
+    let mut temps = Vec::with_capacity(pairs.len());
+    for (src, _) in pairs {
+        let name = src.file_name().unwrap_or_default().to_string_lossy();
+        let temp = generate_unique_name(&src.with_file_name(format!(".{}.renaming", name)));
+        match std::fs::rename(src, &temp) {
+            Ok(()) => {
+                temps.push(Some(temp));
+                outcomes.push(Ok(()));
+            }
+            Err(e) => {
+                temps.push(None);
+                let message = format!("名前変更失敗: {}: {}", src.display(), e);
+                outcomes.push(Err(OpError::io(&e, message)));
+            }
+        }
+    }
+
+    for (((src, dest), temp), outcome) in pairs.iter().zip(temps).zip(outcomes.iter_mut()) {
+        let Some(temp) = temp else {
+            continue;
+        };
+        // 一時的な名前にしている間に同名の項目が作られていたら上書きしない
+        if let Err(e) = rename_no_replace(&temp, dest) {
+            let mut message = format!("名前変更失敗: {}: {}", dest.display(), e);
+            // 入れ替え・循環では元の名前に別の項目が入っていることがあるので、戻すときも上書きしない
+            if rename_no_replace(&temp, src).is_err() {
+                message = format!("{}（元の項目は {} に残っています）", message, temp.display());
+            }
+            *outcome = Err(OpError::io(&e, message));
+        }
+    }
+    outcomes
+}
+
+/// `to` に項目があれば上書きせずに失敗する（`ErrorKind::AlreadyExists`）名前変更
+pub(crate) fn rename_no_replace(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(result) = rename_exclusive(from, to) {
+        return result;
+    }
+    // OS かファイルシステムが対応していなければ、確かめてから変更する
+    if to.symlink_metadata().is_ok() {
+        return Err(std::io::Error::from(std::io::ErrorKind::AlreadyExists));
+    }
+    std::fs::rename(from, to)
+}
+
+/// 確認と変更を 1 回のシステムコールで行う。使えなければ `None`
+#[cfg(target_os = "linux")]
+fn rename_exclusive(from: &Path, to: &Path) -> Option<std::io::Result<()>> {
+    use std::os::unix::ffi::OsStrExt;
+    let from = std::ffi::CString::new(from.as_os_str().as_bytes()).ok()?;
+    let to = std::ffi::CString::new(to.as_os_str().as_bytes()).ok()?;
+    let ret = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            from.as_ptr(),
+            libc::AT_FDCWD,
+            to.as_ptr(),
+            libc::RENAME_NOREPLACE,
+        )
+    };
+    if ret == 0 {
+        return Some(Ok(()));
+    }
+    let e = std::io::Error::last_os_error();
+    match e.raw_os_error() {
+        Some(libc::EINVAL) | Some(libc::ENOSYS) => None,
+        _ => Some(Err(e)),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn rename_exclusive(from: &Path, to: &Path) -> Option<std::io::Result<()>> {
+    use std::os::unix::ffi::OsStrExt;
+    let from = std::ffi::CString::new(from.as_os_str().as_bytes()).ok()?;
+    let to = std::ffi::CString::new(to.as_os_str().as_bytes()).ok()?;
+    let ret = unsafe { libc::renamex_np(from.as_ptr(), to.as_ptr(), libc::RENAME_EXCL) };
+    if ret == 0 {
+        return Some(Ok(()));
+    }
+    let e = std::io::Error::last_os_error();
+    match e.raw_os_error() {
+        Some(libc::EINVAL) | Some(libc::ENOTSUP) => None,
+        _ => Some(Err(e)),
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn rename_exclusive(_from: &Path, _to: &Path) -> Option<std::io::Result<()>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::rename_rules::CounterRule;
+    use std::fs;
+
+    fn paths(dir: &Path, names: &[&str]) -> Vec<String> {
+        names
+            .iter()
+            .map(|n| {
+                let path = dir.join(n);
+                fs::write(&path, *n).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect()
+    }
+
+    fn new_names(plan: &[PlannedRename]) -> Vec<String> {
+        plan.iter()
+            .map(|p| p.dest.file_name().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn regex_replace_with_captures_and_counter() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), &["IMG_0001.JPG", "IMG_0002.JPG"]);
+        let rules = RenameRules {
+            find_replace: Some(FindReplace {
+                find: r"IMG_(\d+)".to_string(),
+                replace: "photo-$1-{n}".to_string(),
+                regex: true,
+                case_sensitive: true,
+            }),
+            counter: CounterRule {
+                start: 9,
+                step: 1,
+                padding: 3,
+            },
+            case: Some(CaseConversion::Lower),
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths, &rules).unwrap();
+        assert_eq!(new_names(&plan), ["photo-0001-009.JPG", "photo-0002-010.JPG"]);
+    }
+
+    #[test]
+    fn literal_replace_prefix_suffix_and_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), &["a.b (draft).txt", "notes"]);
+        let rules = RenameRules {
+            find_replace: Some(FindReplace {
+                find: " (DRAFT)".to_string(),
+                replace: "$".to_string(),
+                regex: false,
+                case_sensitive: false,
+            }),
+            prefix: "new_".to_string(),
+            suffix: "_x".to_string(),
+            extension: Some(".md".to_string()),
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths, &rules).unwrap();
+        assert_eq!(new_names(&plan), ["new_a.b$_x.md", "new_notes_x.md"]);
+
+        let braces = RenameRules {
+            prefix: "{x}{".to_string(),
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths[1..], &braces).unwrap();
+        assert_eq!(new_names(&plan), ["{x}{notes"]);
+    }
+
+    #[test]
+    fn date_token_and_title_case() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), &["hello world.txt"]);
+        let rules = RenameRules {
+            suffix: " {date:%Y}".to_string(),
+            case: Some(CaseConversion::Title),
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths, &rules).unwrap();
+        let year = chrono::Local::now().format("%Y").to_string();
+        assert_eq!(new_names(&plan), [format!("Hello World {}.txt", year)]);
+
+        let bad = RenameRules {
+            suffix: "{date:%Q}".to_string(),
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths, &bad).unwrap();
+        assert_eq!(plan[0].error.as_ref().unwrap().kind, ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn rename_no_replace_keeps_existing_destination() {
+        let dir = tempfile::tempdir().unwrap();
+        let (a, b) = (dir.path().join("a.txt"), dir.path().join("b.txt"));
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+
+        let err = rename_no_replace(&a, &b).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(&b).unwrap(), "b");
+
+        let c = dir.path().join("c.txt");
+        rename_no_replace(&a, &c).unwrap();
+        assert!(!a.exists());
+        assert_eq!(fs::read_to_string(&c).unwrap(), "a");
+    }
+
+    #[test]
+    fn counter_overflow_and_huge_padding_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), &["a.txt", "b.txt"]);
+        let mut rules = RenameRules {
+            suffix: "-{n}".to_string(),
+            counter: CounterRule {
+                start: u64::MAX,
+                step: 1,
+                padding: 0,
+            },
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths, &rules).unwrap();
+        assert!(plan[0].error.is_none());
+        assert_eq!(plan[1].error.as_ref().unwrap().kind, ErrorKind::InvalidInput);
+
+        // 連番を使わなければ桁あふれは問題にならない
+        rules.suffix = "-x".to_string();
+        let plan = plan_batch_rename(&paths, &rules).unwrap();
+        assert!(plan.iter().all(|p| p.error.is_none()));
+
+        rules.counter.padding = usize::MAX;
+        assert!(plan_batch_rename(&paths, &rules).is_err());
+    }
+
+    #[test]
+    fn collisions_are_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), &["a1.txt", "a2.txt", "b.txt"]);
+        fs::write(dir.path().join("c.txt"), "existing").unwrap();
+        let rules = RenameRules {
+            find_replace: Some(FindReplace {
+                find: r"^a\d|^b".to_string(),
+                replace: "c".to_string(),
+                regex: true,
+                case_sensitive: true,
+            }),
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths, &rules).unwrap();
+        assert!(plan.iter().all(|p| p.error.as_ref().unwrap().kind == ErrorKind::AlreadyExists));
+
+        let report = preview_report(plan);
+        assert!(report.items.iter().all(|i| i.status == ItemStatus::Failed));
+        assert!(dir.path().join("a1.txt").exists());
+    }
+
+    #[test]
+    fn swap_and_cycle_renames_apply_safely() {
+        let dir = tempfile::tempdir().unwrap();
+        paths(dir.path(), &["a", "b", "c"]);
+        let pairs: Vec<(PathBuf, PathBuf)> = [("a", "b"), ("b", "c"), ("c", "a")]
+            .iter()
+            .map(|(from, to)| (dir.path().join(from), dir.path().join(to)))
+            .collect();
+        let plan: Vec<PlannedRename> = pairs
+            .iter()
+            .map(|(src, dest)| PlannedRename {
+                src: src.clone(),
+                dest: dest.clone(),
+                error: None,
+            })
+            .collect();
+
+        let report = apply_batch_rename(plan, false).unwrap();
+        assert!(report.items.iter().all(|i| i.status == ItemStatus::Succeeded));
+        assert_eq!(fs::read_to_string(dir.path().join("b")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dir.path().join("c")).unwrap(), "b");
+        assert_eq!(fs::read_to_string(dir.path().join("a")).unwrap(), "c");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+
+    #[test]
+    fn failed_rename_keeps_item_that_took_its_name() {
+        let dir = tempfile::tempdir().unwrap();
+        paths(dir.path(), &["a", "b", "c"]);
+        // b の変更先 c が塞がっているので、a → b の後で b を元に戻せない
+        let pairs: Vec<(PathBuf, PathBuf)> = [("a", "b"), ("b", "c")]
+            .iter()
+            .map(|(from, to)| (dir.path().join(from), dir.path().join(to)))
+            .collect();
+
+        let outcomes = rename_all(&pairs);
+        assert!(outcomes[0].is_ok());
+        let error = outcomes[1].as_ref().unwrap_err();
+        assert_eq!(error.kind, ErrorKind::AlreadyExists);
+        assert_eq!(fs::read_to_string(dir.path().join("b")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dir.path().join("c")).unwrap(), "c");
+        // b の中身は一時的な名前のまま残り、メッセージでその場所を伝える
+        let temp = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .find(|p| p.to_string_lossy().ends_with(".renaming"))
+            .unwrap();
+        assert_eq!(fs::read_to_string(&temp).unwrap(), "b");
+        assert!(error.message.contains(&*temp.to_string_lossy()));
+    }
+
+    #[test]
+    fn apply_reports_renames_done_before_a_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        paths(dir.path(), &["a", "b", "c"]);
+        let plan = [("a", "b"), ("b", "c")]
+            .iter()
+            .map(|(from, to)| PlannedRename {
+                src: dir.path().join(from),
+                dest: dir.path().join(to),
+                error: None,
+            })
+            .collect();
+
+        // 変更を始めてからの失敗では、済んだ項目を元に戻せるよう結果を返す
+        let report = apply_batch_rename(plan, false).unwrap();
+        let statuses: Vec<ItemStatus> = report.items.iter().map(|i| i.status).collect();
+        assert_eq!(statuses, [ItemStatus::Succeeded, ItemStatus::Failed]);
+        assert_eq!(report.items[1].error.as_ref().unwrap().kind, ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn apply_refuses_plan_with_collisions() {
+        let dir = tempfile::tempdir().unwrap();
+        let paths = paths(dir.path(), &["x.txt", "y.txt"]);
+        let rules = RenameRules {
+            extension: Some("md".to_string()),
+            prefix: "z".to_string(),
+            find_replace: Some(FindReplace {
+                find: ".".to_string(),
+                replace: "".to_string(),
+                regex: true,
+                case_sensitive: true,
+            }),
+            ..Default::default()
+        };
+        let plan = plan_batch_rename(&paths, &rules).unwrap();
+        assert!(apply_batch_rename(plan, false).is_err());
+        assert!(dir.path().join("x.txt").exists());
+        assert!(dir.path().join("y.txt").exists());
+    }
+}
@@ -0,0 +1,114 @@
+use crate::history::{Operation, OperationHistory};
+use crate::models::{FileEntry, OpError, TransferOptions};
+use crate::naming::validate_file_name;
+use crate::transfer::{copy_path, generate_unique_name, ProgressTracker};
+use std::path::{Path, PathBuf};
+
+/// 使用するテンプレートフォルダ。未指定なら OS のテンプレートフォルダ（XDG Templates など）
+fn resolve_templates_dir(custom: Option<String>) -> Option<PathBuf> {
+    custom
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(dirs::template_dir)
+}
+
+/// 「新規作成」メニューに出すテンプレート（隠しファイルを除き名前順）
+#[tauri::command]
+pub fn list_templates(templates_dir: Option<String>) -> Vec<FileEntry> {
+    resolve_templates_dir(templates_dir)
+        .map(|dir| list_templates_in(&dir))
+        .unwrap_or_default()
+}
+
+fn list_templates_in(dir: &Path) -> Vec<FileEntry> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<FileEntry> = entries
+        .filter_map(|entry| FileEntry::from_path(&entry.ok()?.path()))
+        .filter(|entry| !entry.is_hidden)
+        .collect();
+    templates.sort_by_key(|entry| entry.name.to_lowercase());
+    templates
+}
+
+/// テンプレートをコピーして新しい項目を作る。
+/// `name` を省略するとテンプレートと同じ名前になり、既にあれば番号を付ける
+#[tauri::command]
+pub fn create_from_template(
+    history: tauri::State<'_, OperationHistory>,
+    template: String,
+    destination: String,
+    name: Option<String>,
+) -> Result<String, OpError> {
+    let created = create_from_template_blocking(&template, &destination, name.as_deref())?;
+    history.record(Operation::CreateFile {
+        path: created.clone(),
+    });
+    Ok(created)
+}
+
+fn create_from_template_blocking(
+    template: &str,
+    destination: &str,
+    name: Option<&str>,
+) -> Result<String, OpError> {
+    let template = Path::new(template);
+    let template_name = template.file_name().unwrap_or_default().to_string_lossy();
+    let name = name.unwrap_or(&template_name);
+    validate_file_name(name)?;
+
+    let mut dest = Path::new(destination).join(name);
+    if dest.symlink_metadata().is_ok() {
+        dest = generate_unique_name(&dest);
+    }
+    copy_path(
+        template,
+        &dest,
+        &TransferOptions::default(),
+        &mut ProgressTracker::silent(),
+    )?;
+    Ok(dest.to_string_lossy().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn lists_visible_templates_by_name() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("b.md"), "").unwrap();
+        fs::write(dir.path().join("A.txt"), "").unwrap();
+        fs::write(dir.path().join(".hidden"), "").unwrap();
+
+        let names: Vec<String> = list_templates_in(dir.path())
+            .into_iter()
+            .map(|e| e.name)
+            .collect();
+        assert_eq!(names, ["A.txt", "b.md"]);
+        assert!(list_templates_in(&dir.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn template_copies_get_unique_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let template = dir.path().join("Report.txt");
+        fs::write(&template, "# title").unwrap();
+        let dest = dir.path().join("work");
+        fs::create_dir(&dest).unwrap();
+
+        let template = template.to_string_lossy();
+        let dest_str = dest.to_string_lossy();
+        let first = create_from_template_blocking(&template, &dest_str, None).unwrap();
+        let second = create_from_template_blocking(&template, &dest_str, None).unwrap();
+        assert_eq!(Path::new(&first), dest.join("Report.txt"));
+        assert_eq!(Path::new(&second), dest.join("Report (2).txt"));
+        assert_eq!(fs::read_to_string(&second).unwrap(), "# title");
+
+        let named = create_from_template_blocking(&template, &dest_str, Some("memo.txt")).unwrap();
+        assert_eq!(Path::new(&named), dest.join("memo.txt"));
+        assert!(create_from_template_blocking(&template, &dest_str, Some("a/b")).is_err());
+    }
+}
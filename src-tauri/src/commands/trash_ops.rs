@@ -0,0 +1,272 @@
+use super::fs_ops::run_blocking;
+use crate::models::{
+    BatchReport, ConflictStrategy, ErrorKind, ItemDetails, ItemResult, ItemStatus, OpError,
+    TrashEntry,
+};
+use crate::transfer::generate_unique_name;
+use std::path::{Path, PathBuf};
+use trash::TrashItem;
+
+#[cfg(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+))]
+use trash::os_limited;
+
+/// ゴミ箱の一覧・復元に対応していない OS（macOS など）用
+#[cfg(not(any(
+    target_os = "windows",
+    all(unix, not(target_os = "macos"), not(target_os = "ios"), not(target_os = "android"))
+)))]
+mod os_limited {
+    use trash::{Error, TrashItem, TrashItemMetadata};
+
+    fn unsupported() -> Error {
+        Error::Unknown {
+            description: "この OS ではゴミ箱の中身を操作できません".to_string(),
+        }
+    }
+
+    pub fn list() -> Result<Vec<TrashItem>, Error> {
+        Err(unsupported())
+    }
+
+    pub fn metadata(_item: &TrashItem) -> Result<TrashItemMetadata, Error> {
+        Err(unsupported())
+    }
+
+    pub fn purge_all<I: IntoIterator<Item = TrashItem>>(_items: I) -> Result<(), Error> {
+        Err(unsupported())
+    }
+
+    pub fn restore_all<I: IntoIterator<Item = TrashItem>>(_items: I) -> Result<(), Error> {
+        Err(unsupported())
+    }
+}
+
+/// 復元先に同名の項目がある場合の処理
+#[derive(Debug, PartialEq, Eq)]
+enum RestoreAction {
+    Restore(PathBuf),
+    /// 既存の項目をゴミ箱へ移動してから元の場所に戻す
+    ReplaceExisting,
+    Skip,
+}
+
+/// ゴミ箱内のファイルとは比較できないので、`OverwriteIfNewer` などはスキップ扱いにする
+fn plan_restore(
+    original: &Path,
+    strategy: Option<ConflictStrategy>,
+) -> Result<RestoreAction, OpError> {
+    if original.symlink_metadata().is_err() {
+        return Ok(RestoreAction::Restore(original.to_path_buf()));
+    }
+    match strategy {
+        None => Err(OpError::new(
+            ErrorKind::AlreadyExists,
+            format!("同名の項目が既に存在します: {}", original.display()),
+        )),
+        Some(ConflictStrategy::Overwrite) => Ok(RestoreAction::ReplaceExisting),
+        Some(ConflictStrategy::Rename) => {
+            Ok(RestoreAction::Restore(generate_unique_name(original)))
+        }
+        Some(_) => Ok(RestoreAction::Skip),
+    }
+}
+
+fn trash_error(message: &str, e: trash::Error) -> String {
+    format!("{}: {}", message, e)
+}
+
+fn list_items() -> Result<Vec<TrashItem>, String> {
+    os_limited::list().map_err(|e| trash_error("ゴミ箱の読み込み失敗", e))
+}
+
+/// 指定された ID の項目を取り出す（見つからない ID は `Err` で返す）
+fn find_items(ids: &[String]) -> Result<Vec<Result<TrashItem, String>>, String> {
+    let mut trashed = list_items()?;
+    Ok(ids
+        .iter()
+        .map(|id| {
+            let index = trashed
+                .iter()
+                .position(|item| item.id.to_string_lossy() == id.as_str())
+                .ok_or_else(|| format!("ゴミ箱に見つかりません: {}", id))?;
+            Ok(trashed.swap_remove(index))
+        })
+        .collect())
+}
+
+fn to_entry(item: &TrashItem) -> TrashEntry {
+    let metadata = os_limited::metadata(item).ok();
+    let item_count = metadata.and_then(|m| m.size.entries());
+    TrashEntry {
+        id: item.id.to_string_lossy().to_string(),
+        name: item.name.to_string_lossy().to_string(),
+        original_path: item.original_path().to_string_lossy().to_string(),
+        deleted_at: (item.time_deleted >= 0).then(|| item.time_deleted * 1000),
+        is_dir: item_count.is_some(),
+        size: metadata.and_then(|m| m.size.size()),
+        item_count,
+    }
+}
+
+/// ゴミ箱内の項目を新しく削除した順に返す
+#[tauri::command]
+pub async fn list_trash() -> Result<Vec<TrashEntry>, String> {
+    run_blocking(|| {
+        let mut items = list_items()?;
+        items.sort_by_key(|item| std::cmp::Reverse(item.time_deleted));
+        Ok(items.iter().map(to_entry).collect())
+    })
+    .await
+}
+
+/// ゴミ箱の項目を元の場所へ戻す。
+/// `strategy` が未指定の場合、元の場所に同名の項目があれば失敗させる
+#[tauri::command]
+pub async fn restore_trash_items(
+    ids: Vec<String>,
+    strategy: Option<String>,
+    continue_on_error: Option<bool>,
+) -> Result<BatchReport, String> {
+    run_blocking(move || {
+        let strategy = strategy.as_deref().map(ConflictStrategy::parse);
+        restore_trash_items_blocking(ids, strategy, continue_on_error.unwrap_or(false))
+    })
+    .await
+}
+
+fn restore_trash_items_blocking(
+    ids: Vec<String>,
+    strategy: Option<ConflictStrategy>,
+    continue_on_error: bool,
+) -> Result<BatchReport, String> {
+    let mut report = BatchReport::default();
+    for (id, found) in ids.iter().zip(find_items(&ids)?) {
+        let result = found
+            .map_err(|message| OpError::new(ErrorKind::NotFound, message))
+            .and_then(|item| restore_item(item, strategy));
+        let (status, destination, error) = match result {
+            Ok(Some(dest)) => (ItemStatus::Succeeded, Some(dest), None),
+            Ok(None) => (ItemStatus::Skipped, None, None),
+            Err(e) => {
+                if !continue_on_error {
+                    return Err(e.message);
+                }
+                (ItemStatus::Failed, None, Some(e))
+            }
+        };
+        report.items.push(ItemResult {
+            source: id.clone(),
+            destination,
+            status,
+            error,
+            details: ItemDetails::default(),
+        });
+    }
+    Ok(report)
+}
+
+/// 1 項目を復元し、戻した先のパスを返す（スキップした場合は `None`）
+fn restore_item(
+    mut item: TrashItem,
+    strategy: Option<ConflictStrategy>,
+) -> Result<Option<String>, OpError> {
+    let original = item.original_path();
+    let dest = match plan_restore(&original, strategy)? {
+        RestoreAction::Skip => return Ok(None),
+        RestoreAction::ReplaceExisting => {
+            trash::delete(&original).map_err(|e| {
+                let message = trash_error("既存の項目をゴミ箱へ移動できません", e);
+                OpError::new(ErrorKind::Other, message)
+            })?;
+            original
+        }
+        RestoreAction::Restore(dest) => dest,
+    };
+    // 復元先は original_parent と name から決まるので、別名で戻す場合は name を差し替える
+    if let Some(name) = dest.file_name() {
+        item.name = name.to_os_string();
+    }
+    os_limited::restore_all([item])
+        .map_err(|e| OpError::new(ErrorKind::Other, trash_error("ゴミ箱からの復元失敗", e)))?;
+    Ok(Some(dest.to_string_lossy().to_string()))
+}
+
+/// ゴミ箱の項目を完全に削除する
+#[tauri::command]
+pub async fn purge_trash_items(ids: Vec<String>) -> Result<(), String> {
+    run_blocking(move || {
+        let items = find_items(&ids)?.into_iter().collect::<Result<Vec<_>, _>>()?;
+        os_limited::purge_all(items).map_err(|e| trash_error("ゴミ箱からの削除失敗", e))
+    })
+    .await
+}
+
+#[tauri::command]
+pub async fn empty_trash() -> Result<(), String> {
+    run_blocking(|| {
+        let items = list_items()?;
+        os_limited::purge_all(items).map_err(|e| trash_error("ゴミ箱を空にできません", e))
+    })
+    .await
+}
+
+/// ゴミ箱に移動した項目を元の場所へ戻す。同じパスの項目が複数あれば最後に捨てたものを戻す
+pub(crate) fn restore_from_trash(paths: &[String]) -> Result<(), String> {
+    let mut trashed = list_items()?;
+    trashed.sort_by_key(|item| std::cmp::Reverse(item.time_deleted));
+
+    let mut selected = Vec::new();
+    for path in paths {
+        let index = trashed
+            .iter()
+            .position(|item| item.original_path() == Path::new(path))
+            .ok_or_else(|| format!("ゴミ箱に見つかりません: {}", path))?;
+        selected.push(trashed.remove(index));
+    }
+    os_limited::restore_all(selected).map_err(|e| trash_error("ゴミ箱からの復元失敗", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn plan_restore_without_conflict_uses_original_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("a.txt");
+        assert_eq!(
+            plan_restore(&original, None).unwrap(),
+            RestoreAction::Restore(original.clone())
+        );
+    }
+
+    #[test]
+    fn plan_restore_resolves_conflicts() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = dir.path().join("a.txt");
+        fs::write(&original, "existing").unwrap();
+
+        let error = plan_restore(&original, None).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::AlreadyExists);
+        assert_eq!(
+            plan_restore(&original, Some(ConflictStrategy::Skip)).unwrap(),
+            RestoreAction::Skip
+        );
+        assert_eq!(
+            plan_restore(&original, Some(ConflictStrategy::OverwriteIfNewer)).unwrap(),
+            RestoreAction::Skip
+        );
+        assert_eq!(
+            plan_restore(&original, Some(ConflictStrategy::Overwrite)).unwrap(),
+            RestoreAction::ReplaceExisting
+        );
+        assert_eq!(
+            plan_restore(&original, Some(ConflictStrategy::Rename)).unwrap(),
+            RestoreAction::Restore(dir.path().join("a (2).txt"))
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! 日時の表示形式。`FileEntry` などの表示用文字列はすべてここで作る
+
+use chrono::{DateTime, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum DateStyle {
+    /// `2024-01-15 10:30`
+    #[default]
+    Iso,
+    /// 表示言語に合わせた形式（`2024/01/15 10:30`・`Jan 15, 2024 10:30`）
+    Locale,
+    /// `3 分前` のような相対表示。1 週間以上前は `Locale` と同じ
+    Relative,
+}
+
+/// フロントエンドの設定から渡される表示形式
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct DateFormatSettings {
+    pub style: DateStyle,
+    /// `ja`・`en` など。未対応の言語は英語で表示する
+    pub language: String,
+}
+
+static SETTINGS: RwLock<DateFormatSettings> = RwLock::new(DateFormatSettings {
+    style: DateStyle::Iso,
+    language: String::new(),
+});
+
+const MINUTE: i64 = 60 * 1000;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+const WEEK: i64 = 7 * DAY;
+
+/// 日時の表示形式を変更する。以降に読み込んだ一覧から反映される
+#[tauri::command]
+pub fn set_date_format(settings: DateFormatSettings) {
+    *SETTINGS.write().unwrap_or_else(|e| e.into_inner()) = settings;
+}
+
+/// UNIX エポックからのミリ秒を、現在の設定で表示用の文字列にする
+pub fn format_timestamp(millis: i64) -> String {
+    let settings = SETTINGS.read().unwrap_or_else(|e| e.into_inner());
+    format_with(&settings, millis, Local::now().timestamp_millis())
+}
+
+fn format_with(settings: &DateFormatSettings, millis: i64, now: i64) -> String {
+    let Some(datetime) = Local.timestamp_millis_opt(millis).single() else {
+        return String::new();
+    };
+    match settings.style {
+        DateStyle::Iso => datetime.format("%Y-%m-%d %H:%M").to_string(),
+        DateStyle::Locale => format_locale(&datetime, &settings.language),
+        DateStyle::Relative => {
+            let elapsed = now - millis;
+            if (0..WEEK).contains(&elapsed) {
+                format_relative(elapsed, &settings.language)
+            } else {
+                format_locale(&datetime, &settings.language)
+            }
+        }
+    }
+}
+
+fn format_locale(datetime: &DateTime<Local>, language: &str) -> String {
+    let pattern = match language {
+        "ja" => "%Y/%m/%d %H:%M",
+        _ => "%b %-d, %Y %H:%M",
+    };
+    datetime.format(pattern).to_string()
+}
+
+fn format_relative(elapsed: i64, language: &str) -> String {
+    let (count, unit) = if elapsed < MINUTE {
+        (0, "")
+    } else if elapsed < HOUR {
+        (elapsed / MINUTE, "minute")
+    } else if elapsed < DAY {
+        (elapsed / HOUR, "hour")
+    } else {
+        (elapsed / DAY, "day")
+    };
+
+    match (language, unit) {
+        ("ja", "") => "たった今".to_string(),
+        ("ja", "minute") => format!("{} 分前", count),
+        ("ja", "hour") => format!("{} 時間前", count),
+        ("ja", _) => format!("{} 日前", count),
+        (_, "") => "just now".to_string(),
+        (_, unit) if count == 1 => format!("1 {} ago", unit),
+        (_, unit) => format!("{} {}s ago", count, unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(style: DateStyle, language: &str) -> DateFormatSettings {
+        DateFormatSettings {
+            style,
+            language: language.to_string(),
+        }
+    }
+
+    fn local_millis(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> i64 {
+        Local
+            .with_ymd_and_hms(y, mo, d, h, mi, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    #[test]
+    fn formats_absolute_styles() {
+        let t = local_millis(2024, 1, 5, 9, 3);
+        assert_eq!(
+            format_with(&settings(DateStyle::Iso, "ja"), t, t),
+            "2024-01-05 09:03"
+        );
+        assert_eq!(
+            format_with(&settings(DateStyle::Locale, "ja"), t, t),
+            "2024/01/05 09:03"
+        );
+        assert_eq!(
+            format_with(&settings(DateStyle::Locale, "en"), t, t),
+            "Jan 5, 2024 09:03"
+        );
+    }
+
+    #[test]
+    fn formats_relative_within_a_week() {
+        let now = local_millis(2024, 6, 20, 12, 0);
+        let ja = settings(DateStyle::Relative, "ja");
+        let en = settings(DateStyle::Relative, "en");
+
+        assert_eq!(format_with(&ja, now - 10 * 1000, now), "たった今");
+        assert_eq!(format_with(&ja, now - 5 * MINUTE, now), "5 分前");
+        assert_eq!(format_with(&en, now - HOUR, now), "1 hour ago");
+        assert_eq!(format_with(&en, now - 3 * DAY, now), "3 days ago");
+        // 1 週間以上前と未来の日時は絶対表示
+        assert_eq!(format_with(&ja, now - 8 * DAY, now), "2024/06/12 12:00");
+        assert_eq!(format_with(&en, now + HOUR, now), "Jun 20, 2024 13:00");
+    }
+}
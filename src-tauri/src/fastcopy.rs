@@ -0,0 +1,163 @@
+//! OS ごとの高速コピー（reflink・スパースファイル）
+
+use std::fs::File;
+use std::io;
+use std::path::Path;
+
+/// コピーオンライトで `src` の中身を共有した `dst` を作る。
+/// 対応していない OS・ファイルシステムではエラーを返すので、呼び出し側で通常のコピーに切り替える
+#[cfg(target_os = "linux")]
+pub fn reflink(src: &Path, dst: &Path) -> io::Result<()> {
+    use std::os::fd::AsRawFd;
+
+    let reader = File::open(src)?;
+    let writer = File::create(dst)?;
+    // SAFETY: どちらも開いているファイルのディスクリプタ
+    let ret = unsafe { libc::ioctl(writer.as_raw_fd(), libc::FICLONE, reader.as_raw_fd()) };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// clonefile はコピー先が既にあると失敗するので、上書きの場合は通常のコピーになる
+#[cfg(target_os = "macos")]
+pub fn reflink(src: &Path, dst: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let src = CString::new(src.as_os_str().as_bytes())?;
+    let dst = CString::new(dst.as_os_str().as_bytes())?;
+    // SAFETY: どちらも NUL 終端済みのパス
+    let ret = unsafe { libc::clonefile(src.as_ptr(), dst.as_ptr(), 0) };
+    if ret == -1 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn reflink(_src: &Path, _dst: &Path) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+/// 穴（ホール）のあるファイルなら、データ部分の範囲 `[start, end)` を返す。
+/// 穴がない・判定できない場合は `None`。どちらの場合も読み込み位置は先頭に戻す
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+pub fn data_segments(file: &File, len: u64) -> io::Result<Option<Vec<(u64, u64)>>> {
+    // SAFETY: 開いているファイルのディスクリプタに対する lseek
+    data_segments_with(file, len, |fd, pos, whence| unsafe { libc::lseek(fd, pos, whence) })
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn data_segments_with(
+    file: &File,
+    len: u64,
+    lseek: impl Fn(libc::c_int, libc::off_t, libc::c_int) -> libc::off_t,
+) -> io::Result<Option<Vec<(u64, u64)>>> {
+    use std::io::Seek;
+    use std::os::fd::AsRawFd;
+    use std::os::unix::fs::MetadataExt;
+
+    // 割り当て済みブロックがサイズに満たなければ穴がある
+    if file.metadata()?.blocks() * 512 >= len {
+        return Ok(None);
+    }
+
+    let fd = file.as_raw_fd();
+    let mut segments = Some(Vec::new());
+    let mut pos = 0;
+    while pos < len {
+        let start = lseek(fd, pos as libc::off_t, libc::SEEK_DATA);
+        if start == -1 {
+            // ENXIO は「これ以降にデータがない」
+            if io::Error::last_os_error().raw_os_error() != Some(libc::ENXIO) {
+                segments = None;
+            }
+            break;
+        }
+        let end = lseek(fd, start, libc::SEEK_HOLE);
+        if end == -1 {
+            segments = None;
+            break;
+        }
+        if let Some(segments) = segments.as_mut() {
+            segments.push((start as u64, end as u64));
+        }
+        pos = end as u64;
+    }
+    // lseek で動いた読み込み位置を戻す。途中で失敗した場合も呼び出し側は先頭から通常のコピーをする
+    let mut file = file;
+    file.rewind()?;
+    Ok(segments)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub fn data_segments(_file: &File, _len: u64) -> io::Result<Option<Vec<(u64, u64)>>> {
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Seek, SeekFrom, Write};
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn data_segments_skips_holes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sparse.img");
+        let mut file = File::create(&path).unwrap();
+        file.seek(SeekFrom::Start(8 * 1024 * 1024)).unwrap();
+        file.write_all(b"tail").unwrap();
+        drop(file);
+
+        let file = File::open(&path).unwrap();
+        let len = file.metadata().unwrap().len();
+        // 穴に対応していないファイルシステムでは None になる
+        if let Some(segments) = data_segments(&file, len).unwrap() {
+            let data: u64 = segments.iter().map(|(s, e)| e - s).sum();
+            assert!(data < len);
+            assert_eq!(segments.last().unwrap().1, len);
+        }
+    }
+
+    #[test]
+    fn data_segments_none_for_dense_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("dense.bin");
+        std::fs::write(&path, vec![1u8; 64 * 1024]).unwrap();
+        let file = File::open(&path).unwrap();
+        assert!(data_segments(&file, 64 * 1024).unwrap().is_none());
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn data_segments_rewinds_when_probe_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sparse.img");
+        let mut file = File::create(&path).unwrap();
+        file.seek(SeekFrom::Start(8 * 1024 * 1024)).unwrap();
+        file.write_all(b"tail").unwrap();
+        drop(file);
+
+        let mut file = File::open(&path).unwrap();
+        let len = file.metadata().unwrap().len();
+        // SEEK_DATA で読み込み位置を動かした後、SEEK_HOLE が失敗したことにする
+        let probed = std::cell::Cell::new(false);
+        let probe = |fd, _, whence| match whence {
+            libc::SEEK_DATA => {
+                probed.set(true);
+                // SAFETY: 開いているファイルのディスクリプタに対する lseek
+                unsafe { libc::lseek(fd, 4096, libc::SEEK_SET) }
+            }
+            _ => -1,
+        };
+        let segments = data_segments_with(&file, len, probe).unwrap();
+        // 穴に対応していないファイルシステムでは lseek まで進まない
+        if probed.get() {
+            assert!(segments.is_none());
+            assert_eq!(file.stream_position().unwrap(), 0);
+        }
+    }
+}
@@ -0,0 +1,461 @@
+//! ファイル操作の履歴（元に戻す・やり直し）。
+//! 完了した操作を、逆の操作ができるだけの情報と一緒に記録する。
+//! 作成した項目を元に戻すとゴミ箱へ移動し、やり直すとゴミ箱から復元する。
+
+use crate::commands::fs_ops::run_blocking;
+use crate::commands::rename_ops::rename_all;
+use crate::commands::trash_ops::restore_from_trash;
+use crate::models::{BatchReport, ItemStatus, SymlinkPolicy, TransferOptions};
+use crate::transfer::{move_path, scan_path, ProgressTracker};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// 元に戻せる操作の上限（古いものから捨てる）
+const MAX_HISTORY: usize = 100;
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MovedItem {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum Operation {
+    Rename { from: String, to: String },
+    Move { items: Vec<MovedItem> },
+    /// 一括名前変更（入れ替え・循環を含むので一括で戻す）
+    BatchRename { items: Vec<MovedItem> },
+    /// コピーで新しく作成した項目（上書き・統合した項目は含めない）
+    Copy { created: Vec<String> },
+    CreateDirectory { path: String },
+    CreateFile { path: String },
+    Trash { paths: Vec<String> },
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub id: u32,
+    pub operation: Operation,
+    /// 操作した日時（UNIX エポックからのミリ秒）
+    pub timestamp: u64,
+}
+
+/// フロントエンドに返す履歴（どちらも新しい順）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryView {
+    pub undo: Vec<HistoryEntry>,
+    pub redo: Vec<HistoryEntry>,
+}
+
+#[derive(Default)]
+struct HistoryState {
+    undo: VecDeque<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+    next_id: u32,
+}
+
+/// アプリ全体で共有する操作履歴
+#[derive(Clone, Default)]
+pub struct OperationHistory {
+    state: Arc<Mutex<HistoryState>>,
+}
+
+impl OperationHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 新しい操作を記録する。やり直しの履歴は消える
+    pub fn record(&self, operation: Operation) {
+        let mut state = self.state.lock().unwrap();
+        state.next_id += 1;
+        let entry = HistoryEntry {
+            id: state.next_id,
+            operation,
+            timestamp: now_millis(),
+        };
+        state.undo.push_back(entry);
+        if state.undo.len() > MAX_HISTORY {
+            state.undo.pop_front();
+        }
+        state.redo.clear();
+    }
+
+    /// コピー結果のうち、新しく作成した項目を記録する。
+    /// 上書きした項目は元の内容に戻せないので、ゴミ箱へ送らないよう記録しない
+    pub fn record_copy(&self, report: &BatchReport) {
+        let created: Vec<String> = succeeded(report)
+            .filter(|item| item.details.created)
+            .filter_map(|item| item.destination.clone())
+            .collect();
+        if !created.is_empty() {
+            self.record(Operation::Copy { created });
+        }
+    }
+
+    pub fn record_move(&self, report: &BatchReport) {
+        let items: Vec<MovedItem> = succeeded(report)
+            .filter_map(|item| {
+                Some(MovedItem {
+                    from: item.source.clone(),
+                    to: item.destination.clone()?,
+                })
+            })
+            .collect();
+        if !items.is_empty() {
+            self.record(Operation::Move { items });
+        }
+    }
+
+    pub fn record_trash(&self, report: &BatchReport) {
+        let paths: Vec<String> = succeeded(report).map(|item| item.source.clone()).collect();
+        if !paths.is_empty() {
+            self.record(Operation::Trash { paths });
+        }
+    }
+
+    /// 最後の操作を元に戻す。失敗した場合は履歴に残したままにする
+    pub fn undo(&self) -> Result<HistoryEntry, String> {
+        let entry = self
+            .state
+            .lock()
+            .unwrap()
+            .undo
+            .pop_back()
+            .ok_or_else(|| "元に戻せる操作がありません".to_string())?;
+        match revert(&entry.operation) {
+            Ok(()) => {
+                self.state.lock().unwrap().redo.push(entry.clone());
+                Ok(entry)
+            }
+            Err(e) => {
+                self.state.lock().unwrap().undo.push_back(entry);
+                Err(e)
+            }
+        }
+    }
+
+    /// 最後に元に戻した操作をやり直す
+    pub fn redo(&self) -> Result<HistoryEntry, String> {
+        let entry = self
+            .state
+            .lock()
+            .unwrap()
+            .redo
+            .pop()
+            .ok_or_else(|| "やり直せる操作がありません".to_string())?;
+        match reapply(&entry.operation) {
+            Ok(()) => {
+                self.state.lock().unwrap().undo.push_back(entry.clone());
+                Ok(entry)
+            }
+            Err(e) => {
+                self.state.lock().unwrap().redo.push(entry);
+                Err(e)
+            }
+        }
+    }
+
+    pub fn list(&self) -> HistoryView {
+        let state = self.state.lock().unwrap();
+        HistoryView {
+            undo: state.undo.iter().rev().cloned().collect(),
+            redo: state.redo.iter().rev().cloned().collect(),
+        }
+    }
+}
+
+fn succeeded(report: &BatchReport) -> impl Iterator<Item = &crate::models::ItemResult> {
+    report
+        .items
+        .iter()
+        .filter(|item| item.status == ItemStatus::Succeeded && !item.details.merged)
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// 操作を取り消す
+fn revert(operation: &Operation) -> Result<(), String> {
+    match operation {
+        Operation::Rename { from, to } => rename_pairs(std::iter::once((to, from))),
+        Operation::Move { items } => {
+            // 後から移動したものから戻す
+            for item in items.iter().rev() {
+                move_back(&item.to, &item.from)?;
+            }
+            Ok(())
+        }
+        Operation::BatchRename { items } => {
+            rename_pairs(items.iter().map(|item| (&item.to, &item.from)))
+        }
+        Operation::Copy { created } => trash_existing(created),
+        Operation::CreateDirectory { path } | Operation::CreateFile { path } => {
+            trash_existing(std::slice::from_ref(path))
+        }
+        Operation::Trash { paths } => restore_missing(paths),
+    }
+}
+
+/// 取り消した操作をもう一度行う
+fn reapply(operation: &Operation) -> Result<(), String> {
+    match operation {
+        Operation::Rename { from, to } => rename_pairs(std::iter::once((from, to))),
+        Operation::Move { items } => {
+            for item in items {
+                move_back(&item.from, &item.to)?;
+            }
+            Ok(())
+        }
+        Operation::BatchRename { items } => {
+            rename_pairs(items.iter().map(|item| (&item.from, &item.to)))
+        }
+        Operation::Copy { created } => restore_missing(created),
+        Operation::CreateDirectory { path } | Operation::CreateFile { path } => {
+            restore_missing(std::slice::from_ref(path))
+        }
+        Operation::Trash { paths } => trash_existing(paths),
+    }
+}
+
+/// `from` を `to` へ移動する。途中で失敗した操作をもう一度実行できるように、
+/// 既に移動済み（`from` がなく `to` がある）なら何もしない
+fn move_back(from: &str, to: &str) -> Result<(), String> {
+    let (from_path, to_path) = (Path::new(from), Path::new(to));
+    let from_exists = from_path.symlink_metadata().is_ok();
+    let to_exists = to_path.symlink_metadata().is_ok();
+    if !from_exists && to_exists {
+        return Ok(());
+    }
+    if !from_exists {
+        return Err(format!("項目が見つかりません: {}", from));
+    }
+    if to_exists {
+        return Err(format!("同名の項目が既に存在します: {}", to));
+    }
+    let totals = scan_path(from_path, SymlinkPolicy::CopyAsLink);
+    move_path(
+        from_path,
+        to_path,
+        totals,
+        &TransferOptions::default(),
+        &mut ProgressTracker::silent(),
+    )
+    .map_err(|e| e.message)
+}
+
+/// まとめて名前を変更する（大文字小文字だけの変更も戻せる）。既に変更済みの項目は飛ばす
+fn rename_pairs<'a>(pairs: impl Iterator<Item = (&'a String, &'a String)>) -> Result<(), String> {
+    let pending: Vec<(PathBuf, PathBuf)> = pairs
+        .map(|(from, to)| (PathBuf::from(from), PathBuf::from(to)))
+        .filter(|(from, to)| from.symlink_metadata().is_ok() || to.symlink_metadata().is_err())
+        .collect();
+    match rename_all(&pending).into_iter().find_map(Result::err) {
+        Some(e) => Err(e.message),
+        None => Ok(()),
+    }
+}
+
+fn trash_existing(paths: &[String]) -> Result<(), String> {
+    let existing: Vec<&String> = paths
+        .iter()
+        .filter(|p| Path::new(p).symlink_metadata().is_ok())
+        .collect();
+    if existing.is_empty() {
+        return Ok(());
+    }
+    trash::delete_all(existing).map_err(|e| format!("ゴミ箱への移動失敗: {}", e))
+}
+
+fn restore_missing(paths: &[String]) -> Result<(), String> {
+    let missing: Vec<String> = paths
+        .iter()
+        .filter(|p| Path::new(p).symlink_metadata().is_err())
+        .cloned()
+        .collect();
+    if missing.is_empty() {
+        return Ok(());
+    }
+    restore_from_trash(&missing)
+}
+
+#[tauri::command]
+pub async fn undo_last_operation(
+    history: tauri::State<'_, OperationHistory>,
+) -> Result<HistoryEntry, String> {
+    let history = history.inner().clone();
+    run_blocking(move || history.undo()).await
+}
+
+#[tauri::command]
+pub async fn redo_operation(
+    history: tauri::State<'_, OperationHistory>,
+) -> Result<HistoryEntry, String> {
+    let history = history.inner().clone();
+    run_blocking(move || history.redo()).await
+}
+
+#[tauri::command]
+pub fn list_operation_history(history: tauri::State<'_, OperationHistory>) -> HistoryView {
+    history.list()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn path_str(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn undo_and_redo_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("old.txt");
+        let to = dir.path().join("new.txt");
+        fs::write(&to, "data").unwrap();
+
+        let history = OperationHistory::new();
+        history.record(Operation::Rename {
+            from: path_str(&from),
+            to: path_str(&to),
+        });
+
+        history.undo().unwrap();
+        assert!(from.exists() && !to.exists());
+        assert_eq!(history.list().redo.len(), 1);
+
+        history.redo().unwrap();
+        assert!(!from.exists() && to.exists());
+        assert_eq!(history.list().undo.len(), 1);
+    }
+
+    #[test]
+    fn undo_move_restores_every_item() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(dest.join("folder")).unwrap();
+        fs::write(dest.join("folder").join("inner.txt"), "inner").unwrap();
+        fs::write(dest.join("a.txt"), "a").unwrap();
+
+        let items = ["a.txt", "folder"]
+            .iter()
+            .map(|name| MovedItem {
+                from: path_str(&src.join(name)),
+                to: path_str(&dest.join(name)),
+            })
+            .collect();
+        let history = OperationHistory::new();
+        history.record(Operation::Move { items });
+
+        history.undo().unwrap();
+        assert_eq!(fs::read_to_string(src.join("a.txt")).unwrap(), "a");
+        assert!(src.join("folder").join("inner.txt").exists());
+        assert!(!dest.join("a.txt").exists());
+    }
+
+    #[test]
+    fn undo_swapped_batch_rename() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        // a と b を入れ替えた後の状態
+        fs::write(&a, "was b").unwrap();
+        fs::write(&b, "was a").unwrap();
+
+        let history = OperationHistory::new();
+        history.record(Operation::BatchRename {
+            items: vec![
+                MovedItem {
+                    from: path_str(&a),
+                    to: path_str(&b),
+                },
+                MovedItem {
+                    from: path_str(&b),
+                    to: path_str(&a),
+                },
+            ],
+        });
+
+        history.undo().unwrap();
+        assert_eq!(fs::read_to_string(&a).unwrap(), "was a");
+        assert_eq!(fs::read_to_string(&b).unwrap(), "was b");
+        history.redo().unwrap();
+        assert_eq!(fs::read_to_string(&a).unwrap(), "was b");
+    }
+
+    #[test]
+    fn failed_undo_stays_in_history() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("old.txt");
+        let to = dir.path().join("new.txt");
+        fs::write(&from, "other").unwrap();
+        fs::write(&to, "data").unwrap();
+
+        let history = OperationHistory::new();
+        history.record(Operation::Rename {
+            from: path_str(&from),
+            to: path_str(&to),
+        });
+
+        assert!(history.undo().is_err());
+        assert_eq!(fs::read_to_string(&from).unwrap(), "other");
+        assert_eq!(history.list().undo.len(), 1);
+        assert!(history.list().redo.is_empty());
+    }
+
+    #[test]
+    fn new_operation_clears_redo() {
+        let dir = tempfile::tempdir().unwrap();
+        let from = dir.path().join("a.txt");
+        let to = dir.path().join("b.txt");
+        fs::write(&to, "data").unwrap();
+
+        let history = OperationHistory::new();
+        history.record(Operation::Rename {
+            from: path_str(&from),
+            to: path_str(&to),
+        });
+        history.undo().unwrap();
+        history.record(Operation::CreateDirectory {
+            path: path_str(&dir.path().join("new")),
+        });
+
+        let view = history.list();
+        assert!(view.redo.is_empty());
+        assert_eq!(view.undo.len(), 1);
+        assert!(history.redo().is_err());
+    }
+
+    #[test]
+    fn history_is_capped() {
+        let history = OperationHistory::new();
+        for i in 0..MAX_HISTORY + 5 {
+            history.record(Operation::CreateDirectory {
+                path: format!("/tmp/dir{}", i),
+            });
+        }
+        let view = history.list();
+        assert_eq!(view.undo.len(), MAX_HISTORY);
+        assert_eq!(
+            view.undo[0].operation,
+            Operation::CreateDirectory {
+                path: format!("/tmp/dir{}", MAX_HISTORY + 4)
+            }
+        );
+    }
+}
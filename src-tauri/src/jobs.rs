@@ -0,0 +1,853 @@
+use crate::commands::fs_ops::{
+    copy_items_blocking, copy_items_with_strategy_blocking, copy_planned_blocking,
+    delete_items_blocking, move_items_blocking,
+};
+use crate::history::OperationHistory;
+use crate::models::{BatchFailure, BatchReport, DeleteMode, TransferOptions, TransferProgress};
+use crate::resume::{self, InterruptedJob, Journal};
+use crate::transfer::{JobControl, ProgressTracker, CANCELLED_MESSAGE};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// ジョブの状態変化・進捗を通知するイベント名
+pub const JOB_EVENT: &str = "job_updated";
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum JobKind {
+    Copy,
+    Move,
+    Delete,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Paused,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    fn is_finished(self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Cancelled)
+    }
+}
+
+/// フロントエンドに返すジョブ情報
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JobInfo {
+    pub id: u32,
+    pub kind: JobKind,
+    pub status: JobStatus,
+    pub sources: Vec<String>,
+    pub destination: Option<String>,
+    pub progress: Option<TransferProgress>,
+    /// 削除ジョブの削除方法
+    pub delete_mode: Option<DeleteMode>,
+    /// 終了時の項目ごとの結果。失敗・キャンセルしたジョブでは中断するまでの分
+    pub report: Option<BatchReport>,
+    pub error: Option<String>,
+}
+
+/// ジョブの実行内容
+#[derive(Debug, Clone)]
+pub struct JobSpec {
+    pub kind: JobKind,
+    pub sources: Vec<String>,
+    pub destination: Option<String>,
+    /// 衝突解決（未指定ならコピーは上書き、移動は失敗）
+    pub strategy: Option<String>,
+    pub options: TransferOptions,
+    /// コピージョブの再開用ジャーナル。既に存在すれば中断したジョブの続きとして実行する
+    pub journal: Option<PathBuf>,
+    /// 削除ジョブの削除方法
+    pub delete_mode: Option<DeleteMode>,
+}
+
+type Notifier = Arc<dyn Fn(&JobInfo) + Send + Sync>;
+
+struct JobEntry {
+    info: JobInfo,
+    spec: JobSpec,
+    control: Arc<JobControl>,
+    started: bool,
+}
+
+/// アプリ全体で共有するファイル操作ジョブのキュー。
+/// 同じデバイスを対象とするジョブは 1 つずつ順番に実行する。
+pub struct JobManager {
+    jobs: Arc<Mutex<HashMap<u32, JobEntry>>>,
+    /// デバイスごとの待ち行列。キーが存在する間はワーカースレッドが動いている
+    queues: Arc<Mutex<HashMap<String, VecDeque<u32>>>>,
+    next_id: Mutex<u32>,
+}
+
+impl JobManager {
+    pub fn new() -> Self {
+        Self {
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            queues: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Mutex::new(1),
+        }
+    }
+
+    pub fn submit(&self, spec: JobSpec, notify: Notifier) -> u32 {
+        let id = {
+            let mut next = self.next_id.lock().unwrap();
+            let id = *next;
+            *next += 1;
+            id
+        };
+
+        let info = JobInfo {
+            id,
+            kind: spec.kind,
+            status: JobStatus::Queued,
+            sources: spec.sources.clone(),
+            destination: spec.destination.clone(),
+            progress: None,
+            delete_mode: spec.delete_mode,
+            report: None,
+            error: None,
+        };
+        notify(&info);
+
+        let device = device_key(&spec);
+        self.jobs.lock().unwrap().insert(
+            id,
+            JobEntry {
+                info,
+                spec,
+                control: Arc::new(JobControl::new()),
+                started: false,
+            },
+        );
+
+        let mut queues = self.queues.lock().unwrap();
+        if let Some(queue) = queues.get_mut(&device) {
+            queue.push_back(id);
+        } else {
+            queues.insert(device.clone(), VecDeque::from([id]));
+            let jobs = self.jobs.clone();
+            let queues = self.queues.clone();
+            std::thread::spawn(move || run_queue(device, jobs, queues, notify));
+        }
+
+        id
+    }
+
+    pub fn list(&self) -> Vec<JobInfo> {
+        let jobs = self.jobs.lock().unwrap();
+        let mut list: Vec<JobInfo> = jobs.values().map(|j| j.info.clone()).collect();
+        list.sort_by_key(|j| j.id);
+        list
+    }
+
+    pub fn pause(&self, id: u32) -> Result<JobInfo, String> {
+        self.update(id, |entry| {
+            if entry.info.status.is_finished() {
+                return Err(format!("ジョブ {} は既に終了しています", id));
+            }
+            entry.control.pause();
+            entry.info.status = JobStatus::Paused;
+            Ok(())
+        })
+    }
+
+    pub fn resume(&self, id: u32) -> Result<JobInfo, String> {
+        self.update(id, |entry| {
+            if entry.info.status != JobStatus::Paused {
+                return Err(format!("ジョブ {} は一時停止していません", id));
+            }
+            entry.control.resume();
+            entry.info.status = if entry.started {
+                JobStatus::Running
+            } else {
+                JobStatus::Queued
+            };
+            Ok(())
+        })
+    }
+
+    pub fn cancel(&self, id: u32) -> Result<JobInfo, String> {
+        self.update(id, |entry| {
+            if entry.info.status.is_finished() {
+                return Err(format!("ジョブ {} は既に終了しています", id));
+            }
+            entry.control.cancel();
+            // 未着手のジョブはその場でキャンセル扱いにする
+            if !entry.started {
+                entry.info.status = JobStatus::Cancelled;
+            }
+            Ok(())
+        })
+    }
+
+    /// 実行待ち・実行中のジョブが使っているジャーナル
+    fn journals_in_use(&self) -> Vec<PathBuf> {
+        self.jobs
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|entry| !entry.info.status.is_finished())
+            .filter_map(|entry| entry.spec.journal.clone())
+            .collect()
+    }
+
+    /// 終了したジョブを一覧から取り除く
+    pub fn clear_finished(&self) {
+        self.jobs
+            .lock()
+            .unwrap()
+            .retain(|_, entry| !entry.info.status.is_finished());
+    }
+
+    fn update(
+        &self,
+        id: u32,
+        f: impl FnOnce(&mut JobEntry) -> Result<(), String>,
+    ) -> Result<JobInfo, String> {
+        let mut jobs = self.jobs.lock().unwrap();
+        let entry = jobs
+            .get_mut(&id)
+            .ok_or_else(|| format!("ジョブ {} が見つかりません", id))?;
+        f(entry)?;
+        Ok(entry.info.clone())
+    }
+}
+
+/// 1 デバイス分の待ち行列を空になるまで順に処理する
+fn run_queue(
+    device: String,
+    jobs: Arc<Mutex<HashMap<u32, JobEntry>>>,
+    queues: Arc<Mutex<HashMap<String, VecDeque<u32>>>>,
+    notify: Notifier,
+) {
+    loop {
+        let next = {
+            let mut queues = queues.lock().unwrap();
+            let next = queues.get_mut(&device).and_then(|q| q.pop_front());
+            if next.is_none() {
+                queues.remove(&device);
+            }
+            next
+        };
+        let Some(id) = next else {
+            break;
+        };
+
+        let (spec, control) = {
+            let mut jobs = jobs.lock().unwrap();
+            let Some(entry) = jobs.get_mut(&id) else {
+                continue;
+            };
+            if entry.control.is_cancelled() {
+                continue;
+            }
+            entry.started = true;
+            if entry.info.status == JobStatus::Queued {
+                entry.info.status = JobStatus::Running;
+            }
+            notify(&entry.info);
+            (entry.spec.clone(), entry.control.clone())
+        };
+
+        let progress_jobs = jobs.clone();
+        let progress_notify = notify.clone();
+        let tracker = ProgressTracker::new(
+            Some(id.to_string()),
+            Box::new(move |progress| {
+                if let Some(entry) = progress_jobs.lock().unwrap().get_mut(&id) {
+                    entry.info.progress = Some(progress.clone());
+                    progress_notify(&entry.info);
+                }
+            }),
+        )
+        .with_control(control.clone());
+        let (mut tracker, resumed_plan) = match open_journal(&spec) {
+            Some(journal) => {
+                let plan = journal.previous_plan(spec.options.symlinks);
+                (tracker.with_journal(journal), plan)
+            }
+            None => (tracker, None),
+        };
+
+        // 一時停止されたまま開始した場合はここで再開を待つ
+        let result = tracker
+            .checkpoint()
+            .map_err(BatchFailure::from)
+            .and_then(|_| match resumed_plan {
+                Some(plan) => copy_planned_blocking(plan, &spec.options, &mut tracker),
+                None => run_job(&spec, &mut tracker),
+            });
+        // 終了したジョブは再開しないのでジャーナルを消す（アプリの終了・クラッシュ時だけ残る）
+        drop(tracker);
+        if let Some(path) = &spec.journal {
+            let _ = std::fs::remove_file(path);
+        }
+
+        let mut jobs = jobs.lock().unwrap();
+        if let Some(entry) = jobs.get_mut(&id) {
+            match result {
+                Ok(report) => {
+                    entry.info.status = JobStatus::Completed;
+                    entry.info.report = Some(report);
+                }
+                // 中断までに済んだ項目も元に戻せるよう、結果は残す
+                Err(failure) if control.is_cancelled() => {
+                    entry.info.status = JobStatus::Cancelled;
+                    entry.info.report = Some(failure.report);
+                    entry.info.error = Some(CANCELLED_MESSAGE.to_string());
+                }
+                Err(failure) => {
+                    entry.info.status = JobStatus::Failed;
+                    entry.info.report = Some(failure.report);
+                    entry.info.error = Some(failure.error.message);
+                }
+            }
+            notify(&entry.info);
+        }
+    }
+}
+
+/// コピージョブのジャーナルを開く。書けない場所なら再開できないだけでジョブは実行する
+fn open_journal(spec: &JobSpec) -> Option<Journal> {
+    let path = spec.journal.as_ref().filter(|_| spec.kind == JobKind::Copy)?;
+    if path.exists() {
+        return Journal::reopen(path).ok();
+    }
+    Journal::create(
+        path,
+        &spec.sources,
+        spec.destination.as_deref().unwrap_or_default(),
+        spec.strategy.as_deref(),
+        &spec.options,
+    )
+    .ok()
+}
+
+fn run_job(spec: &JobSpec, tracker: &mut ProgressTracker) -> Result<BatchReport, BatchFailure> {
+    let sources = spec.sources.clone();
+    let destination = spec.destination.clone().unwrap_or_default();
+    let options = spec.options.clone();
+    match (spec.kind, &spec.strategy) {
+        (JobKind::Copy, None) => copy_items_blocking(sources, destination, options, tracker),
+        (JobKind::Copy, Some(strategy)) => copy_items_with_strategy_blocking(
+            sources,
+            destination,
+            strategy.clone(),
+            options,
+            tracker,
+        ),
+        (JobKind::Move, strategy) => {
+            move_items_blocking(sources, destination, strategy.clone(), options, tracker)
+        }
+        (JobKind::Delete, _) => delete_items_blocking(
+            sources,
+            spec.delete_mode.unwrap_or_default(),
+            options.continue_on_error,
+            tracker,
+        ),
+    }
+}
+
+/// ジョブを振り分けるデバイスのキー。
+/// コピー・移動は書き込み先、削除は対象のあるデバイスでまとめる。
+fn device_key(spec: &JobSpec) -> String {
+    let target = spec
+        .destination
+        .as_deref()
+        .or_else(|| spec.sources.first().map(String::as_str))
+        .unwrap_or_default();
+    device_of(Path::new(target))
+}
+
+#[cfg(unix)]
+fn device_of(path: &Path) -> String {
+    use std::os::unix::fs::MetadataExt;
+    // 削除対象など存在しないパスは親ディレクトリで判定する
+    path.ancestors()
+        .find_map(|p| p.metadata().ok())
+        .map(|m| format!("dev:{}", m.dev()))
+        .unwrap_or_else(|| path.to_string_lossy().to_string())
+}
+
+#[cfg(not(unix))]
+fn device_of(path: &Path) -> String {
+    // Windows はドライブ（プレフィックス）単位でまとめる
+    match path.components().next() {
+        Some(std::path::Component::Prefix(prefix)) => {
+            prefix.as_os_str().to_string_lossy().to_uppercase()
+        }
+        _ => path.to_string_lossy().to_string(),
+    }
+}
+
+fn journal_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join(resume::JOURNAL_DIR))
+        .map_err(|e| format!("データフォルダが取得できません: {}", e))
+}
+
+/// 終了したジョブは、失敗・キャンセルしたものも済んだ項目を元に戻せるように操作履歴に記録する
+fn app_notifier(app: AppHandle) -> Notifier {
+    Arc::new(move |info| {
+        if let Some(report) = info.report.as_ref().filter(|_| info.status.is_finished()) {
+            let history = app.state::<OperationHistory>();
+            match info.kind {
+                JobKind::Copy => history.record_copy(report),
+                JobKind::Move => history.record_move(report),
+                JobKind::Delete if info.delete_mode == Some(DeleteMode::Trash) => {
+                    history.record_trash(report)
+                }
+                JobKind::Delete => {}
+            }
+        }
+        let _ = app.emit(JOB_EVENT, info.clone());
+    })
+}
+
+#[tauri::command]
+pub fn submit_copy_job(
+    app: AppHandle,
+    state: tauri::State<'_, JobManager>,
+    sources: Vec<String>,
+    destination: String,
+    strategy: Option<String>,
+    options: Option<TransferOptions>,
+) -> Result<u32, String> {
+    let spec = JobSpec {
+        kind: JobKind::Copy,
+        sources,
+        destination: Some(destination),
+        strategy,
+        options: options.unwrap_or_default(),
+        journal: journal_dir(&app).ok().map(|dir| resume::new_journal_path(&dir)),
+        delete_mode: None,
+    };
+    Ok(state.submit(spec, app_notifier(app)))
+}
+
+#[tauri::command]
+pub fn submit_move_job(
+    app: AppHandle,
+    state: tauri::State<'_, JobManager>,
+    sources: Vec<String>,
+    destination: String,
+    strategy: Option<String>,
+    options: Option<TransferOptions>,
+) -> Result<u32, String> {
+    let spec = JobSpec {
+        kind: JobKind::Move,
+        sources,
+        destination: Some(destination),
+        strategy,
+        options: options.unwrap_or_default(),
+        journal: None,
+        delete_mode: None,
+    };
+    Ok(state.submit(spec, app_notifier(app)))
+}
+
+#[tauri::command]
+pub fn submit_delete_job(
+    app: AppHandle,
+    state: tauri::State<'_, JobManager>,
+    paths: Vec<String>,
+    continue_on_error: Option<bool>,
+    mode: Option<DeleteMode>,
+) -> Result<u32, String> {
+    let spec = JobSpec {
+        kind: JobKind::Delete,
+        sources: paths,
+        destination: None,
+        strategy: None,
+        options: TransferOptions {
+            continue_on_error: continue_on_error.unwrap_or(false),
+            ..Default::default()
+        },
+        journal: None,
+        delete_mode: Some(mode.unwrap_or_default()),
+    };
+    Ok(state.submit(spec, app_notifier(app)))
+}
+
+#[tauri::command]
+pub fn list_jobs(state: tauri::State<'_, JobManager>) -> Vec<JobInfo> {
+    state.list()
+}
+
+#[tauri::command]
+pub fn pause_job(state: tauri::State<'_, JobManager>, job_id: u32) -> Result<JobInfo, String> {
+    state.pause(job_id)
+}
+
+#[tauri::command]
+pub fn resume_job(state: tauri::State<'_, JobManager>, job_id: u32) -> Result<JobInfo, String> {
+    state.resume(job_id)
+}
+
+#[tauri::command]
+pub fn cancel_job(state: tauri::State<'_, JobManager>, job_id: u32) -> Result<JobInfo, String> {
+    state.cancel(job_id)
+}
+
+#[tauri::command]
+pub fn clear_finished_jobs(state: tauri::State<'_, JobManager>) {
+    state.clear_finished();
+}
+
+/// 前回の起動時に中断したコピージョブ
+#[tauri::command]
+pub fn list_interrupted_jobs(
+    app: AppHandle,
+    state: tauri::State<'_, JobManager>,
+) -> Result<Vec<InterruptedJob>, String> {
+    let dir = journal_dir(&app)?;
+    Ok(resume::list_interrupted(&dir, &state.journals_in_use()))
+}
+
+/// 中断したコピージョブを、完了済みのファイルを飛ばして再開する
+#[tauri::command]
+pub fn resume_interrupted_job(
+    app: AppHandle,
+    state: tauri::State<'_, JobManager>,
+    journal_id: String,
+) -> Result<u32, String> {
+    let path = resume::journal_path(&journal_dir(&app)?, &journal_id)?;
+    if state.journals_in_use().contains(&path) {
+        return Err(format!("ジョブは既に再開されています: {}", journal_id));
+    }
+    let job = resume::read_interrupted(&path)?;
+    let spec = JobSpec {
+        kind: JobKind::Copy,
+        sources: job.sources,
+        destination: Some(job.destination),
+        strategy: job.strategy,
+        options: job.options,
+        journal: Some(path),
+        delete_mode: None,
+    };
+    Ok(state.submit(spec, app_notifier(app)))
+}
+
+/// 中断したコピージョブを再開せずに破棄する
+#[tauri::command]
+pub fn discard_interrupted_job(
+    app: AppHandle,
+    state: tauri::State<'_, JobManager>,
+    journal_id: String,
+) -> Result<(), String> {
+    let path = resume::journal_path(&journal_dir(&app)?, &journal_id)?;
+    if state.journals_in_use().contains(&path) {
+        return Err(format!("ジョブは実行中です: {}", journal_id));
+    }
+    std::fs::remove_file(&path).map_err(|e| format!("ジャーナルの削除失敗: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transfer::{PlannedItem, PlannedTransfer};
+    use std::fs;
+    use std::time::{Duration, Instant};
+
+    fn silent_notifier() -> Notifier {
+        Arc::new(|_| {})
+    }
+
+    fn wait_finished(mgr: &JobManager, id: u32) -> JobInfo {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        loop {
+            let info = mgr.list().into_iter().find(|j| j.id == id).unwrap();
+            if info.status.is_finished() {
+                return info;
+            }
+            assert!(Instant::now() < deadline, "job {} did not finish", id);
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    fn copy_spec(src: &Path, dest: &Path) -> JobSpec {
+        JobSpec {
+            kind: JobKind::Copy,
+            sources: vec![src.to_string_lossy().to_string()],
+            destination: Some(dest.to_string_lossy().to_string()),
+            strategy: None,
+            options: TransferOptions::default(),
+            journal: None,
+            delete_mode: None,
+        }
+    }
+
+    #[test]
+    fn job_manager_new_creates_empty() {
+        let mgr = JobManager::new();
+        assert!(mgr.list().is_empty());
+        assert_eq!(*mgr.next_id.lock().unwrap(), 1);
+    }
+
+    #[test]
+    fn copy_job_completes() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let mgr = JobManager::new();
+        let id = mgr.submit(copy_spec(&src, &dest), silent_notifier());
+        let info = wait_finished(&mgr, id);
+
+        assert_eq!(info.status, JobStatus::Completed);
+        assert_eq!(info.progress.unwrap().items_done, 1);
+        assert_eq!(fs::read_to_string(dest.join("a.txt")).unwrap(), "data");
+    }
+
+    #[test]
+    fn copy_job_journal_removed_when_finished() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+        let journal = dir.path().join("journal").join("1.jsonl");
+
+        let mgr = JobManager::new();
+        let spec = JobSpec {
+            journal: Some(journal.clone()),
+            ..copy_spec(&src, &dest)
+        };
+        let id = mgr.submit(spec, silent_notifier());
+        assert_eq!(wait_finished(&mgr, id).status, JobStatus::Completed);
+        assert!(!journal.exists());
+    }
+
+    #[test]
+    fn interrupted_copy_job_resumes_from_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("data");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("done.txt"), "done").unwrap();
+        fs::write(src.join("partial.bin"), "0123456789").unwrap();
+        fs::write(src.join("todo.txt"), "todo").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(dest.join("data")).unwrap();
+
+        // 前回の実行: done.txt は完了、partial.bin は 4 バイト書いたところで中断
+        let spec = copy_spec(&src, &dest);
+        let path = dir.path().join("journal").join("1.jsonl");
+        let mut journal = Journal::create(
+            &path,
+            &spec.sources,
+            spec.destination.as_deref().unwrap(),
+            None,
+            &spec.options,
+        )
+        .unwrap();
+        journal.record_plan(&[PlannedItem::Transfer(PlannedTransfer::new(
+            &src,
+            dest.join("data"),
+            spec.options.symlinks,
+        ))]);
+        for name in ["done.txt", "partial.bin"] {
+            journal.record_started(&src.join(name), &fs::metadata(src.join(name)).unwrap());
+        }
+        journal.record_done(&src.join("done.txt"));
+        journal.record_truncated(&src.join("partial.bin"));
+        drop(journal);
+        // 再開で書き直されていないことが分かるよう、既存分は元と違う内容にしておく
+        fs::write(dest.join("data").join("done.txt"), "DONE").unwrap();
+        fs::write(dest.join("data").join("partial.bin"), "ABCD").unwrap();
+
+        let interrupted = resume::list_interrupted(path.parent().unwrap(), &[]);
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].files_done, 1);
+
+        let mgr = JobManager::new();
+        let resumed = JobSpec {
+            journal: Some(path.clone()),
+            ..spec
+        };
+        let id = mgr.submit(resumed, silent_notifier());
+        assert_eq!(wait_finished(&mgr, id).status, JobStatus::Completed);
+
+        let out = dest.join("data");
+        assert_eq!(fs::read_to_string(out.join("done.txt")).unwrap(), "DONE");
+        assert_eq!(fs::read_to_string(out.join("partial.bin")).unwrap(), "ABCD456789");
+        assert_eq!(fs::read_to_string(out.join("todo.txt")).unwrap(), "todo");
+        assert!(!dest.join("data (2)").exists());
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn jobs_on_same_device_run_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let order = Arc::new(Mutex::new(Vec::new()));
+        let sink = order.clone();
+        let notifier: Notifier = Arc::new(move |info| {
+            if info.status == JobStatus::Running && info.progress.is_none() {
+                sink.lock().unwrap().push(info.id);
+            }
+        });
+
+        let mgr = JobManager::new();
+        let first = mgr.submit(copy_spec(&src, &dest), notifier.clone());
+        let second = mgr.submit(copy_spec(&src, &dest), notifier);
+        wait_finished(&mgr, first);
+        wait_finished(&mgr, second);
+
+        assert_eq!(*order.lock().unwrap(), vec![first, second]);
+    }
+
+    /// ワーカーを起動せずにジョブを積む（一時停止状態などを確定させてから実行するため）
+    fn enqueue(mgr: &JobManager, spec: JobSpec) -> u32 {
+        let id = {
+            let mut next = mgr.next_id.lock().unwrap();
+            let id = *next;
+            *next += 1;
+            id
+        };
+        let info = JobInfo {
+            id,
+            kind: spec.kind,
+            status: JobStatus::Queued,
+            sources: spec.sources.clone(),
+            destination: spec.destination.clone(),
+            progress: None,
+            delete_mode: spec.delete_mode,
+            report: None,
+            error: None,
+        };
+        mgr.jobs.lock().unwrap().insert(
+            id,
+            JobEntry {
+                info,
+                spec,
+                control: Arc::new(JobControl::new()),
+                started: false,
+            },
+        );
+        mgr.queues
+            .lock()
+            .unwrap()
+            .entry("test".to_string())
+            .or_default()
+            .push_back(id);
+        id
+    }
+
+    fn start_worker(mgr: &JobManager) {
+        let jobs = mgr.jobs.clone();
+        let queues = mgr.queues.clone();
+        std::thread::spawn(move || run_queue("test".to_string(), jobs, queues, silent_notifier()));
+    }
+
+    fn status_of(mgr: &JobManager, id: u32) -> JobStatus {
+        mgr.list().into_iter().find(|j| j.id == id).unwrap().status
+    }
+
+    #[test]
+    fn paused_job_blocks_queue_until_resumed() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let mgr = JobManager::new();
+        let first = enqueue(&mgr, copy_spec(&src, &dest));
+        let second = enqueue(&mgr, copy_spec(&src, &dest));
+        mgr.pause(first).unwrap();
+        start_worker(&mgr);
+
+        std::thread::sleep(Duration::from_millis(50));
+        assert_eq!(status_of(&mgr, first), JobStatus::Paused);
+        assert_eq!(status_of(&mgr, second), JobStatus::Queued);
+        assert!(!dest.join("a.txt").exists());
+
+        let resumed = mgr.resume(first).unwrap();
+        assert_eq!(resumed.status, JobStatus::Running);
+        assert_eq!(wait_finished(&mgr, first).status, JobStatus::Completed);
+        assert_eq!(wait_finished(&mgr, second).status, JobStatus::Completed);
+    }
+
+    #[test]
+    fn cancel_queued_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        fs::write(&a, "a").unwrap();
+        fs::write(&b, "b").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let mgr = JobManager::new();
+        let first = enqueue(&mgr, copy_spec(&a, &dest));
+        let second = enqueue(&mgr, copy_spec(&b, &dest));
+        let cancelled = mgr.cancel(second).unwrap();
+        assert_eq!(cancelled.status, JobStatus::Cancelled);
+        start_worker(&mgr);
+
+        assert_eq!(wait_finished(&mgr, first).status, JobStatus::Completed);
+        assert!(dest.join("a.txt").exists());
+        assert!(!dest.join("b.txt").exists());
+    }
+
+    #[test]
+    fn cancel_paused_running_job() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let mgr = JobManager::new();
+        let id = enqueue(&mgr, copy_spec(&src, &dest));
+        mgr.pause(id).unwrap();
+        start_worker(&mgr);
+        std::thread::sleep(Duration::from_millis(20));
+        mgr.cancel(id).unwrap();
+
+        let info = wait_finished(&mgr, id);
+        assert_eq!(info.status, JobStatus::Cancelled);
+        assert_eq!(info.error.as_deref(), Some(CANCELLED_MESSAGE));
+        assert!(!dest.join("a.txt").exists());
+    }
+
+    #[test]
+    fn cancel_finished_job_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+
+        let mgr = JobManager::new();
+        let id = mgr.submit(copy_spec(&src, dir.path()), silent_notifier());
+        wait_finished(&mgr, id);
+
+        assert!(mgr.cancel(id).is_err());
+        mgr.clear_finished();
+        assert!(mgr.list().is_empty());
+    }
+
+    #[test]
+    fn unknown_job_returns_error() {
+        let mgr = JobManager::new();
+        let result = mgr.pause(42);
+        assert!(result.unwrap_err().contains("見つかりません"));
+    }
+}
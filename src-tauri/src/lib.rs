@@ -1,8 +1,23 @@
 mod commands;
+mod date_format;
+mod fastcopy;
+mod history;
+mod jobs;
+mod listing;
 mod models;
+mod naming;
+mod natural_sort;
+mod permissions;
+mod resume;
+mod shred;
 mod terminal;
+mod transfer;
+#[cfg(unix)]
+mod users;
 
 use commands::*;
+use history::OperationHistory;
+use jobs::JobManager;
 use terminal::PtyManager;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -15,6 +30,9 @@ pub fn run() {
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_drag::init())
         .manage(PtyManager::new())
+        .manage(JobManager::new())
+        .manage(OperationHistory::new())
+        .manage(DirectoryStreams::new())
         .setup(|app| {
             use tauri::Manager;
 
@@ -46,14 +64,30 @@ pub fn run() {
         })
         .invoke_handler(tauri::generate_handler![
             read_directory,
+            read_directory_stream,
+            cancel_directory_stream,
             get_home_dir,
             copy_items,
             move_items,
             delete_items,
+            list_trash,
+            restore_trash_items,
+            purge_trash_items,
+            empty_trash,
             rename_item,
+            batch_rename,
             create_directory,
+            create_file,
+            list_templates,
+            create_from_template,
+            create_symlink,
+            create_hardlink,
             search_files,
             read_file_preview,
+            get_file_properties,
+            set_permissions,
+            set_owner,
+            date_format::set_date_format,
             terminal::terminal_spawn,
             terminal::terminal_write,
             terminal::terminal_resize,
@@ -63,6 +97,20 @@ pub fn run() {
             copy_items_with_strategy,
             read_clipboard_files,
             write_clipboard_files,
+            jobs::submit_copy_job,
+            jobs::submit_move_job,
+            jobs::submit_delete_job,
+            jobs::list_jobs,
+            jobs::pause_job,
+            jobs::resume_job,
+            jobs::cancel_job,
+            jobs::clear_finished_jobs,
+            jobs::list_interrupted_jobs,
+            jobs::resume_interrupted_job,
+            jobs::discard_interrupted_job,
+            history::undo_last_operation,
+            history::redo_operation,
+            history::list_operation_history,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,413 @@
+//! フォルダ一覧への `ListOptions` の適用（絞り込み・並べ替え・グループ分け）。
+//! 名前と種類だけで済む場合は stat せずに並べ、メタデータは `into_entry` で必要になってから読む
+
+use crate::models::{FileEntry, GroupBy, ListOptions, SortKey};
+use crate::natural_sort::{compare_folded, fold};
+use chrono::{DateTime, Datelike, Local};
+use std::cmp::Ordering;
+use std::fs::DirEntry;
+use std::path::{Path, PathBuf};
+
+/// 一覧に載せる候補
+pub struct Candidate {
+    path: PathBuf,
+    name: String,
+    /// 比較用に正規化した名前
+    sort_name: String,
+    is_dir: bool,
+    entry: Option<FileEntry>,
+    group: Option<String>,
+}
+
+impl Candidate {
+    fn from_dir_entry(dir_entry: &DirEntry) -> Option<Self> {
+        let file_type = dir_entry.file_type().ok()?;
+        let path = dir_entry.path();
+        // シンボリックリンクだけはリンク先を見ないとフォルダか分からない
+        let is_dir = file_type.is_dir() || (file_type.is_symlink() && path.is_dir());
+        Some(Self {
+            name: dir_entry.file_name().to_string_lossy().to_string(),
+            sort_name: String::new(),
+            path,
+            is_dir,
+            entry: None,
+            group: None,
+        })
+    }
+
+    /// メタデータを読み込む。途中で消えていれば false
+    fn load(&mut self) -> bool {
+        if self.entry.is_none() {
+            self.entry = FileEntry::from_path(&self.path);
+        }
+        self.entry.is_some()
+    }
+
+    /// 一覧の項目にする（まだなら stat する）。途中で消えていれば `None`
+    pub fn into_entry(mut self) -> Option<FileEntry> {
+        self.load();
+        let mut entry = self.entry?;
+        entry.group = self.group;
+        Some(entry)
+    }
+
+    fn extension(&self) -> String {
+        if self.is_dir {
+            return String::new();
+        }
+        Path::new(&self.name)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    }
+
+    fn size(&self) -> u64 {
+        self.entry.as_ref().map_or(0, |e| e.size)
+    }
+
+    fn modified_at(&self) -> Option<i64> {
+        self.entry.as_ref().and_then(|e| e.modified_at)
+    }
+}
+
+/// 絞り込みと並べ替えを済ませた候補の一覧。`should_stop` が true を返したら `None`
+pub fn prepare(
+    reader: std::fs::ReadDir,
+    options: &ListOptions,
+    should_stop: impl Fn() -> bool,
+) -> Result<Option<Vec<Candidate>>, String> {
+    let filter = NameFilter::new(options)?;
+    let needs_metadata = matches!(options.sort_by, SortKey::Size | SortKey::Modified)
+        || options.group_by == Some(GroupBy::Date);
+    let now = Local::now();
+
+    let mut candidates = Vec::new();
+    for dir_entry in reader {
+        if should_stop() {
+            return Ok(None);
+        }
+        let Some(dir_entry) = dir_entry.ok() else {
+            continue;
+        };
+        if !options.show_hidden && is_hidden(&dir_entry) {
+            continue;
+        }
+        let Some(mut candidate) = Candidate::from_dir_entry(&dir_entry) else {
+            continue;
+        };
+        if !candidate.is_dir && !filter.matches(&candidate.name) {
+            continue;
+        }
+        if needs_metadata && !candidate.load() {
+            continue;
+        }
+        candidate.sort_name = fold(&candidate.name, options.fold_kana);
+        candidate.group = options.group_by.map(|g| group_key(&candidate, g, &now));
+        candidates.push(candidate);
+    }
+
+    candidates.sort_by(|a, b| compare(a, b, options));
+    Ok(Some(candidates))
+}
+
+/// 読み込みを始める前に、指定が正しいかだけ確かめる
+pub fn validate(options: &ListOptions) -> Result<(), String> {
+    NameFilter::new(options).map(|_| ())
+}
+
+/// `prepare` と `into_entry` をまとめて行う（すべて stat する）
+pub fn list_entries(
+    reader: std::fs::ReadDir,
+    options: &ListOptions,
+) -> Result<Vec<FileEntry>, String> {
+    let candidates = prepare(reader, options, || false)?.unwrap_or_default();
+    Ok(candidates
+        .into_iter()
+        .filter_map(Candidate::into_entry)
+        .collect())
+}
+
+struct NameFilter {
+    patterns: Vec<glob::Pattern>,
+    extensions: Vec<String>,
+}
+
+impl NameFilter {
+    fn new(options: &ListOptions) -> Result<Self, String> {
+        let patterns = options
+            .patterns
+            .iter()
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                glob::Pattern::new(p)
+                    .map_err(|e| format!("パターンが正しくありません: {}: {}", p, e))
+            })
+            .collect::<Result<_, _>>()?;
+        let extensions = options
+            .extensions
+            .iter()
+            .map(|e| e.trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect();
+        Ok(Self {
+            patterns,
+            extensions,
+        })
+    }
+
+    fn matches(&self, name: &str) -> bool {
+        let match_options = glob::MatchOptions {
+            case_sensitive: false,
+            require_literal_separator: false,
+            require_literal_leading_dot: false,
+        };
+        let pattern_ok = self.patterns.is_empty()
+            || self
+                .patterns
+                .iter()
+                .any(|p| p.matches_with(name, match_options));
+        let extension_ok = self.extensions.is_empty()
+            || Path::new(name)
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .is_some_and(|e| self.extensions.contains(&e));
+        pattern_ok && extension_ok
+    }
+}
+
+#[cfg(windows)]
+fn is_hidden(dir_entry: &DirEntry) -> bool {
+    // Windows では DirEntry のメタデータは列挙時に取得済みで、追加の stat はかからない
+    let name = dir_entry.file_name().to_string_lossy().to_string();
+    dir_entry
+        .metadata()
+        .map(|m| crate::models::file_entry::is_hidden_file(&name, &m))
+        .unwrap_or(false)
+}
+
+#[cfg(not(windows))]
+fn is_hidden(dir_entry: &DirEntry) -> bool {
+    dir_entry.file_name().to_string_lossy().starts_with('.')
+}
+
+const DATE_GROUPS: &[&str] = &[
+    "today",
+    "yesterday",
+    "thisWeek",
+    "thisMonth",
+    "thisYear",
+    "older",
+    "unknown",
+];
+
+fn group_key(candidate: &Candidate, group_by: GroupBy, now: &DateTime<Local>) -> String {
+    match group_by {
+        GroupBy::Type if candidate.is_dir => "folder".to_string(),
+        GroupBy::Type => candidate.extension(),
+        GroupBy::Date => date_group(candidate.modified_at(), now).to_string(),
+    }
+}
+
+/// 週は月曜始まり。未来の日時は `unknown` に入れる
+fn date_group(modified_at: Option<i64>, now: &DateTime<Local>) -> &'static str {
+    let Some(modified) = modified_at.and_then(DateTime::from_timestamp_millis) else {
+        return "unknown";
+    };
+    let modified = modified.with_timezone(&Local);
+    let days = (now.date_naive() - modified.date_naive()).num_days();
+    let weekday = i64::from(now.weekday().num_days_from_monday());
+    match days {
+        ..=-1 => "unknown",
+        0 => "today",
+        1 => "yesterday",
+        _ if days <= weekday => "thisWeek",
+        _ if modified.year() == now.year() && modified.month() == now.month() => "thisMonth",
+        _ if modified.year() == now.year() => "thisYear",
+        _ => "older",
+    }
+}
+
+/// グループの並び。種類は フォルダ → 拡張子のアルファベット順 → 拡張子なし、日付は新しい順
+fn compare_groups(a: &str, b: &str, group_by: GroupBy) -> Ordering {
+    match group_by {
+        GroupBy::Type => {
+            let rank = |key: &str| match key {
+                "folder" => 0,
+                "" => 2,
+                _ => 1,
+            };
+            rank(a).cmp(&rank(b)).then_with(|| a.cmp(b))
+        }
+        GroupBy::Date => {
+            let rank = |key: &str| DATE_GROUPS.iter().position(|g| *g == key);
+            rank(a).cmp(&rank(b))
+        }
+    }
+}
+
+fn compare(a: &Candidate, b: &Candidate, options: &ListOptions) -> Ordering {
+    let group = match (options.group_by, &a.group, &b.group) {
+        (Some(group_by), Some(x), Some(y)) => compare_groups(x, y, group_by),
+        _ => Ordering::Equal,
+    };
+    let dirs = if options.dirs_first {
+        b.is_dir.cmp(&a.is_dir)
+    } else {
+        Ordering::Equal
+    };
+    let by_name = || compare_folded(&a.sort_name, &b.sort_name).then_with(|| a.name.cmp(&b.name));
+    let key = match options.sort_by {
+        SortKey::Name => by_name(),
+        SortKey::Size => a.size().cmp(&b.size()).then_with(by_name),
+        SortKey::Modified => a.modified_at().cmp(&b.modified_at()).then_with(by_name),
+        SortKey::Type => a.extension().cmp(&b.extension()).then_with(by_name),
+    };
+    let key = if options.descending {
+        key.reverse()
+    } else {
+        key
+    };
+    group.then(dirs).then(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::fs;
+
+    fn names(dir: &Path, options: &ListOptions) -> Vec<String> {
+        list_entries(fs::read_dir(dir).unwrap(), options)
+            .unwrap()
+            .into_iter()
+            .map(|e| e.name)
+            .collect()
+    }
+
+    fn setup() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("file10.txt"), "1234567890").unwrap();
+        fs::write(dir.path().join("file2.txt"), "12").unwrap();
+        fs::write(dir.path().join("b.rs"), "12345").unwrap();
+        fs::write(dir.path().join(".hidden"), "").unwrap();
+        fs::create_dir(dir.path().join("zdir")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn default_puts_dirs_first_in_natural_order() {
+        let dir = setup();
+        assert_eq!(
+            names(dir.path(), &ListOptions::default()),
+            ["zdir", ".hidden", "b.rs", "file2.txt", "file10.txt"]
+        );
+    }
+
+    #[test]
+    fn kana_folding_is_optional() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["カ2.txt", "か10.txt", "き.txt"] {
+            fs::write(dir.path().join(name), "").unwrap();
+        }
+        assert_eq!(
+            names(dir.path(), &ListOptions::default()),
+            ["か10.txt", "き.txt", "カ2.txt"]
+        );
+        let options = ListOptions {
+            fold_kana: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(dir.path(), &options),
+            ["カ2.txt", "か10.txt", "き.txt"]
+        );
+    }
+
+    #[test]
+    fn sorts_by_size_descending_without_dirs_first() {
+        let dir = setup();
+        let options = ListOptions {
+            sort_by: SortKey::Size,
+            descending: true,
+            dirs_first: false,
+            show_hidden: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(dir.path(), &options),
+            ["file10.txt", "b.rs", "file2.txt", "zdir"]
+        );
+    }
+
+    #[test]
+    fn filters_by_glob_and_extension_but_keeps_dirs() {
+        let dir = setup();
+        let options = ListOptions {
+            patterns: vec!["FILE*".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            names(dir.path(), &options),
+            ["zdir", "file2.txt", "file10.txt"]
+        );
+
+        let options = ListOptions {
+            extensions: vec![".RS".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(names(dir.path(), &options), ["zdir", "b.rs"]);
+
+        let options = ListOptions {
+            patterns: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(list_entries(fs::read_dir(dir.path()).unwrap(), &options).is_err());
+    }
+
+    #[test]
+    fn groups_by_type_are_contiguous() {
+        let dir = setup();
+        let options = ListOptions {
+            group_by: Some(GroupBy::Type),
+            show_hidden: false,
+            ..Default::default()
+        };
+        let entries = list_entries(fs::read_dir(dir.path()).unwrap(), &options).unwrap();
+        let groups: Vec<(&str, &str)> = entries
+            .iter()
+            .map(|e| (e.group.as_deref().unwrap(), e.name.as_str()))
+            .collect();
+        assert_eq!(
+            groups,
+            [
+                ("folder", "zdir"),
+                ("rs", "b.rs"),
+                ("txt", "file2.txt"),
+                ("txt", "file10.txt")
+            ]
+        );
+    }
+
+    #[test]
+    fn date_groups_follow_calendar_days() {
+        let now = Local.with_ymd_and_hms(2024, 6, 20, 12, 0, 0).unwrap();
+        let at = |y, m, d| {
+            Some(
+                Local
+                    .with_ymd_and_hms(y, m, d, 9, 0, 0)
+                    .unwrap()
+                    .timestamp_millis(),
+            )
+        };
+        assert_eq!(date_group(at(2024, 6, 20), &now), "today");
+        assert_eq!(date_group(at(2024, 6, 19), &now), "yesterday");
+        // 2024-06-20 は木曜日なので、週は 06-17（月曜日）から
+        assert_eq!(date_group(at(2024, 6, 17), &now), "thisWeek");
+        assert_eq!(date_group(at(2024, 6, 16), &now), "thisMonth");
+        assert_eq!(date_group(at(2024, 6, 2), &now), "thisMonth");
+        assert_eq!(date_group(at(2024, 1, 2), &now), "thisYear");
+        assert_eq!(date_group(at(2023, 12, 31), &now), "older");
+        assert_eq!(date_group(None, &now), "unknown");
+        assert_eq!(date_group(at(2024, 6, 21), &now), "unknown");
+    }
+}
@@ -0,0 +1,183 @@
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// 衝突したファイルの一方の情報（比較ダイアログ用）
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictSide {
+    pub is_dir: bool,
+    pub size: u64,
+    /// 更新日時（UNIX エポックからのミリ秒）
+    pub modified: Option<i64>,
+}
+
+impl ConflictSide {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Self {
+            is_dir: metadata.is_dir(),
+            size: if metadata.is_dir() { 0 } else { metadata.len() },
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_millis() as i64),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyConflict {
+    pub name: String,
+    pub source: String,
+    pub destination: String,
+    pub source_info: ConflictSide,
+    pub destination_info: ConflictSide,
+}
+
+/// フォルダのマージ中に見つかった、フォルダ内の衝突
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct NestedConflict {
+    /// 衝突したコピー先のパス
+    pub path: String,
+    pub resolution: Resolution,
+    /// `Rename` で実際にコピーした先
+    pub renamed_to: Option<String>,
+}
+
+/// コピー先に同名の項目がある場合の解決方法
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ConflictStrategy {
+    Skip,
+    Overwrite,
+    Rename,
+    /// コピー元の方が新しい場合のみ上書き（それ以外はスキップ）
+    OverwriteIfNewer,
+    /// サイズが異なる場合のみ上書き（それ以外はスキップ）
+    OverwriteIfSizeDiffers,
+}
+
+/// 1 項目に対して実際に行う処理
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum Resolution {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+impl ConflictStrategy {
+    /// コマンド引数の文字列を解釈する。未知の値は従来どおり上書き扱い
+    pub fn parse(s: &str) -> Self {
+        match s {
+            "skip" => Self::Skip,
+            "rename" => Self::Rename,
+            "overwriteIfNewer" => Self::OverwriteIfNewer,
+            "overwriteIfSizeDiffers" => Self::OverwriteIfSizeDiffers,
+            _ => Self::Overwrite,
+        }
+    }
+
+    /// 条件付き上書きは両方の情報を比べて決める。ディレクトリ同士は上書きと同じ扱い
+    pub fn resolve(self, src: &Path, dest: &Path) -> Resolution {
+        match self {
+            Self::Skip => Resolution::Skip,
+            Self::Overwrite => Resolution::Overwrite,
+            Self::Rename => Resolution::Rename,
+            Self::OverwriteIfNewer | Self::OverwriteIfSizeDiffers => {
+                let (Some(src_info), Some(dest_info)) =
+                    (ConflictSide::from_path(src), ConflictSide::from_path(dest))
+                else {
+                    return Resolution::Overwrite;
+                };
+                if src_info.is_dir && dest_info.is_dir {
+                    return Resolution::Overwrite;
+                }
+                let overwrite = if self == Self::OverwriteIfNewer {
+                    src_info.modified > dest_info.modified
+                } else {
+                    src_info.size != dest_info.size
+                };
+                if overwrite {
+                    Resolution::Overwrite
+                } else {
+                    Resolution::Skip
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::time::{Duration, SystemTime};
+
+    fn set_mtime(path: &Path, time: SystemTime) {
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[test]
+    fn parse_known_strategies() {
+        assert_eq!(ConflictStrategy::parse("skip"), ConflictStrategy::Skip);
+        assert_eq!(ConflictStrategy::parse("rename"), ConflictStrategy::Rename);
+        assert_eq!(
+            ConflictStrategy::parse("overwriteIfNewer"),
+            ConflictStrategy::OverwriteIfNewer
+        );
+        assert_eq!(ConflictStrategy::parse("unknown"), ConflictStrategy::Overwrite);
+    }
+
+    #[test]
+    fn overwrite_if_newer_compares_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.txt");
+        let dest = dir.path().join("dest.txt");
+        fs::write(&src, "a").unwrap();
+        fs::write(&dest, "b").unwrap();
+        let now = SystemTime::now();
+        set_mtime(&src, now);
+        set_mtime(&dest, now - Duration::from_secs(60));
+
+        let strategy = ConflictStrategy::OverwriteIfNewer;
+        assert_eq!(strategy.resolve(&src, &dest), Resolution::Overwrite);
+        assert_eq!(strategy.resolve(&dest, &src), Resolution::Skip);
+    }
+
+    #[test]
+    fn overwrite_if_size_differs_compares_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        let c = dir.path().join("c.txt");
+        fs::write(&a, "same").unwrap();
+        fs::write(&b, "diff").unwrap();
+        fs::write(&c, "longer").unwrap();
+
+        let strategy = ConflictStrategy::OverwriteIfSizeDiffers;
+        assert_eq!(strategy.resolve(&a, &b), Resolution::Skip);
+        assert_eq!(strategy.resolve(&a, &c), Resolution::Overwrite);
+    }
+
+    #[test]
+    fn conflict_side_reports_size_and_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "hello").unwrap();
+
+        let side = ConflictSide::from_path(&file).unwrap();
+        assert!(!side.is_dir);
+        assert_eq!(side.size, 5);
+        assert!(side.modified.unwrap() > 0);
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// 削除の方法
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum DeleteMode {
+    /// ゴミ箱へ移動する（元に戻せる）
+    #[default]
+    Trash,
+    /// ゴミ箱を使わずに削除する
+    Permanent,
+    /// ファイルの中身を上書きしてから削除する
+    Shred,
+}
@@ -1,4 +1,6 @@
+use crate::date_format::format_timestamp;
 use serde::Serialize;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -9,11 +11,18 @@ pub struct FileEntry {
     pub is_symlink: bool,
     pub is_hidden: bool,
     pub size: u64,
+    /// 表示用の更新日時（形式は `date_format` の設定に従う）
     pub modified: Option<String>,
+    /// 以下 3 つは UNIX エポックからのミリ秒。取得できなければ `None`
+    pub modified_at: Option<i64>,
+    pub created_at: Option<i64>,
+    pub accessed_at: Option<i64>,
     pub mime_type: Option<String>,
+    /// `ListOptions::group_by` を指定したときのグループのキー
+    pub group: Option<String>,
 }
 
-fn is_hidden_file(name: &str, metadata: &std::fs::Metadata) -> bool {
+pub(crate) fn is_hidden_file(name: &str, metadata: &std::fs::Metadata) -> bool {
     #[cfg(windows)]
     {
         use std::os::windows::fs::MetadataExt;
@@ -43,13 +52,8 @@ impl FileEntry {
         let is_dir = real_metadata.is_dir();
         let size = if is_dir { 0 } else { real_metadata.len() };
 
-        let modified = real_metadata
-            .modified()
-            .ok()
-            .map(|t| {
-                let datetime: chrono::DateTime<chrono::Local> = t.into();
-                datetime.format("%Y-%m-%d %H:%M").to_string()
-            });
+        let modified_at = epoch_millis(real_metadata.modified());
+        let modified = modified_at.map(format_timestamp);
 
         let mime_type = if is_dir {
             None
@@ -65,11 +69,21 @@ impl FileEntry {
             is_hidden,
             size,
             modified,
+            modified_at,
+            created_at: epoch_millis(real_metadata.created()),
+            accessed_at: epoch_millis(real_metadata.accessed()),
             mime_type,
+            group: None,
         })
     }
 }
 
+pub(crate) fn epoch_millis(time: std::io::Result<SystemTime>) -> Option<i64> {
+    time.ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as i64)
+}
+
 pub(crate) fn guess_mime(name: &str) -> Option<String> {
     let ext = name.rsplit('.').next()?.to_lowercase();
     let mime = match ext.as_str() {
@@ -149,6 +163,8 @@ mod tests {
         assert!(!entry.is_hidden);
         assert_eq!(entry.size, 5);
         assert!(entry.modified.is_some());
+        assert!(entry.modified_at.is_some_and(|ms| ms > 0));
+        assert!(entry.accessed_at.is_some());
         assert_eq!(entry.mime_type, Some("text/plain".to_string()));
     }
 
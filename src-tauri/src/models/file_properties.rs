@@ -0,0 +1,149 @@
+use super::FileEntry;
+use serde::Serialize;
+use std::fs::Metadata;
+use std::path::Path;
+
+/// プロパティダイアログ用の詳細情報。日時は `entry` の `modified_at` などを使う。
+/// シンボリックリンクはリンク先の情報を返す（リンク切れならリンク自身）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FileProperties {
+    pub entry: FileEntry,
+    /// パーミッションビット（setuid などを含む下位 12 ビット）。Unix のみ
+    pub mode: Option<u32>,
+    pub uid: Option<u32>,
+    pub gid: Option<u32>,
+    pub owner: Option<String>,
+    pub group: Option<String>,
+    pub inode: Option<u64>,
+    pub device: Option<u64>,
+    pub hard_links: Option<u64>,
+    /// シンボリックリンクが指しているパス（リンクに書かれたまま）
+    pub link_target: Option<String>,
+    pub is_broken_link: bool,
+    pub is_executable: bool,
+    pub readonly: bool,
+}
+
+impl FileProperties {
+    pub fn from_path(path: &Path) -> std::io::Result<Self> {
+        let link_metadata = path.symlink_metadata()?;
+        let entry = FileEntry::from_path(path).ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, "名前を取得できません")
+        })?;
+
+        let (link_target, is_broken_link) = if link_metadata.is_symlink() {
+            let target = std::fs::read_link(path)?;
+            (
+                Some(target.to_string_lossy().to_string()),
+                std::fs::metadata(path).is_err(),
+            )
+        } else {
+            (None, false)
+        };
+        let metadata = std::fs::metadata(path).unwrap_or(link_metadata);
+
+        #[cfg_attr(not(unix), allow(unused_mut))]
+        let mut properties = Self {
+            is_executable: is_executable(&entry.name, &metadata),
+            readonly: metadata.permissions().readonly(),
+            entry,
+            mode: None,
+            uid: None,
+            gid: None,
+            owner: None,
+            group: None,
+            inode: None,
+            device: None,
+            hard_links: None,
+            link_target,
+            is_broken_link,
+        };
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            properties.mode = Some(metadata.mode() & 0o7777);
+            properties.uid = Some(metadata.uid());
+            properties.gid = Some(metadata.gid());
+            properties.owner = crate::users::user_name(metadata.uid());
+            properties.group = crate::users::group_name(metadata.gid());
+            properties.inode = Some(metadata.ino());
+            properties.device = Some(metadata.dev());
+            properties.hard_links = Some(metadata.nlink());
+        }
+        Ok(properties)
+    }
+}
+
+#[cfg(unix)]
+fn is_executable(_name: &str, metadata: &Metadata) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    !metadata.is_dir() && metadata.permissions().mode() & 0o111 != 0
+}
+
+#[cfg(not(unix))]
+fn is_executable(name: &str, metadata: &Metadata) -> bool {
+    const EXECUTABLE_EXTENSIONS: &[&str] = &["exe", "com", "bat", "cmd", "ps1", "msi"];
+    !metadata.is_dir()
+        && Path::new(name)
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| EXECUTABLE_EXTENSIONS.contains(&ext.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn reports_timestamps_and_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "hello").unwrap();
+
+        let props = FileProperties::from_path(&file).unwrap();
+        assert_eq!(props.entry.size, 5);
+        assert!(!props.is_executable);
+        assert!(!props.is_broken_link);
+        assert_eq!(props.link_target, None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_unix_metadata() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("run.sh");
+        fs::write(&script, "#!/bin/sh").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o754)).unwrap();
+        fs::hard_link(&script, dir.path().join("run2.sh")).unwrap();
+
+        let props = FileProperties::from_path(&script).unwrap();
+        assert_eq!(props.mode, Some(0o754));
+        assert!(props.is_executable);
+        assert_eq!(props.hard_links, Some(2));
+        assert!(props.inode.is_some());
+        assert_eq!(props.uid, Some(unsafe { libc::getuid() }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn reports_symlink_target_and_broken_links() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("real.txt"), "x").unwrap();
+        let ok = dir.path().join("ok");
+        let broken = dir.path().join("broken");
+        std::os::unix::fs::symlink("real.txt", &ok).unwrap();
+        std::os::unix::fs::symlink("missing.txt", &broken).unwrap();
+
+        let props = FileProperties::from_path(&ok).unwrap();
+        assert_eq!(props.link_target.as_deref(), Some("real.txt"));
+        assert!(!props.is_broken_link);
+        assert_eq!(props.entry.size, 1);
+
+        let props = FileProperties::from_path(&broken).unwrap();
+        assert_eq!(props.link_target.as_deref(), Some("missing.txt"));
+        assert!(props.is_broken_link);
+    }
+}
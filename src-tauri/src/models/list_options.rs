@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+/// フォルダ一覧の並べ替え・絞り込み・グループ分け（省略時は フォルダ優先・名前順・すべて表示）
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ListOptions {
+    pub sort_by: SortKey,
+    pub descending: bool,
+    /// フォルダをファイルより前に並べる（降順でも先頭のまま）
+    pub dirs_first: bool,
+    pub show_hidden: bool,
+    /// ファイル名の glob（`*.rs` など、大文字小文字を区別しない）。
+    /// いずれかに一致するファイルだけ残す。フォルダには適用しない
+    pub patterns: Vec<String>,
+    /// 残す拡張子（先頭の `.` は省略可）。フォルダには適用しない
+    pub extensions: Vec<String>,
+    pub group_by: Option<GroupBy>,
+    /// 名前の比較でひらがなとカタカナ、全角と半角を区別しない
+    pub fold_kana: bool,
+}
+
+impl Default for ListOptions {
+    fn default() -> Self {
+        Self {
+            sort_by: SortKey::Name,
+            descending: false,
+            dirs_first: true,
+            show_hidden: true,
+            patterns: Vec::new(),
+            extensions: Vec::new(),
+            group_by: None,
+            fold_kana: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SortKey {
+    /// 数字の部分を数値として比べる（`file2` < `file10`）。Unicode の正規化形の違いは無視する
+    #[default]
+    Name,
+    Size,
+    Modified,
+    /// 拡張子、同じなら名前
+    Type,
+}
+
+/// グループ分けの単位。各項目の `group` にグループのキーが入り、同じグループの項目は連続する
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum GroupBy {
+    /// `folder` か拡張子（小文字）。拡張子がなければ空文字
+    Type,
+    /// 更新日時で `today`・`yesterday`・`thisWeek`・`thisMonth`・`thisYear`・`older`・`unknown`。
+    /// `thisWeek` は月曜日から一昨日まで、未来の日時と取得できない日時は `unknown`
+    Date,
+}
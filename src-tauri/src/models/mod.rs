@@ -1,3 +1,26 @@
+pub mod conflict;
+pub mod delete_mode;
 pub mod file_entry;
+pub mod file_properties;
+pub mod list_options;
+pub mod op_result;
+pub mod permission_rules;
+pub mod progress;
+pub mod rename_rules;
+pub mod transfer_options;
+pub mod trash_entry;
 
+pub use conflict::{ConflictSide, ConflictStrategy, CopyConflict, NestedConflict, Resolution};
+pub use delete_mode::DeleteMode;
 pub use file_entry::FileEntry;
+pub use file_properties::FileProperties;
+pub use list_options::{GroupBy, ListOptions, SortKey};
+pub use op_result::{
+    BatchFailure, BatchReport, CopyMethod, ErrorKind, ItemDetails, ItemResult, ItemState,
+    ItemStatus, OpError,
+};
+pub use permission_rules::PermissionRules;
+pub use progress::TransferProgress;
+pub use rename_rules::{CaseConversion, FindReplace, RenameRules};
+pub use transfer_options::{PreserveOptions, SymlinkPolicy, TransferOptions};
+pub use trash_entry::TrashEntry;
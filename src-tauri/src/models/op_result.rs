@@ -0,0 +1,178 @@
+use super::NestedConflict;
+use serde::Serialize;
+
+/// 失敗の種類。フロントエンドはこれを見て再試行や代替操作を提示する
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ErrorKind {
+    NotFound,
+    PermissionDenied,
+    AlreadyExists,
+    NoSpace,
+    CrossDevice,
+    InvalidInput,
+    Cancelled,
+    /// コピー後の検証でコピー元とコピー先の内容が一致しなかった
+    ChecksumMismatch,
+    /// ゴミ箱に移動できなかった（ゴミ箱のないボリュームなど）。完全削除なら実行できる
+    TrashUnavailable,
+    /// 名前に使用できない文字（Windows・FAT で使えない文字を含む）
+    InvalidCharacter,
+    /// 名前にパスの区切り文字が含まれている
+    PathSeparator,
+    /// CON などの予約されている名前
+    ReservedName,
+    NameTooLong,
+    /// ファイルシステムや OS が対応していない操作（リンクなど）
+    Unsupported,
+    Other,
+}
+
+impl From<std::io::ErrorKind> for ErrorKind {
+    fn from(kind: std::io::ErrorKind) -> Self {
+        use std::io::ErrorKind as Io;
+        match kind {
+            Io::NotFound => Self::NotFound,
+            Io::PermissionDenied => Self::PermissionDenied,
+            Io::AlreadyExists => Self::AlreadyExists,
+            Io::StorageFull => Self::NoSpace,
+            Io::CrossesDevices => Self::CrossDevice,
+            Io::InvalidInput | Io::InvalidFilename => Self::InvalidInput,
+            Io::Unsupported => Self::Unsupported,
+            _ => Self::Other,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OpError {
+    pub kind: ErrorKind,
+    pub message: String,
+    /// 失敗した時点で操作対象がどうなっているか（分かる場合のみ）
+    pub state: Option<ItemState>,
+}
+
+/// 移動に失敗した項目が残された状態
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemState {
+    /// 移動元はそのままで、移動先には何も残っていない
+    SourceIntact,
+    /// 書きかけの移動先を削除できなかった（移動元はそのまま）
+    PartialDestination,
+    /// 移動先は完成したが、移動元を削除しきれなかった
+    BothPresent,
+    /// フォルダの統合中に失敗し、一部の項目だけ移動した
+    PartlyMoved,
+}
+
+impl OpError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            state: None,
+        }
+    }
+
+    pub fn with_state(mut self, state: ItemState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// I/O エラーの種類を引き継いでメッセージを付ける
+    pub fn io(e: &std::io::Error, message: impl Into<String>) -> Self {
+        Self::new(e.kind().into(), message)
+    }
+}
+
+impl From<OpError> for String {
+    fn from(e: OpError) -> Self {
+        e.message
+    }
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ItemStatus {
+    Succeeded,
+    Skipped,
+    Failed,
+}
+
+/// 処理できた項目の付加情報
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemDetails {
+    /// フォルダのマージで解決した衝突
+    pub conflicts: Vec<NestedConflict>,
+    /// 実際に使われたコピー方法（rename だけで済んだ移動では空）
+    pub copy_methods: Vec<CopyMethod>,
+    /// 既存のフォルダへマージした（元に戻す対象にしない）
+    #[serde(skip)]
+    pub merged: bool,
+    /// コピー前にコピー先がなかった。元に戻すときに消してよいのはこの項目だけ
+    #[serde(skip)]
+    pub created: bool,
+}
+
+/// ファイルの中身をコピーした方法
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "camelCase")]
+pub enum CopyMethod {
+    /// コピーオンライトで中身を共有した（btrfs・XFS・APFS など）
+    Reflink,
+    /// 穴（ホール）を保ったままデータ部分だけコピーした
+    Sparse,
+    /// 全バイトを読み書きした
+    Standard,
+}
+
+/// 1 つの操作対象に対する結果
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemResult {
+    pub source: String,
+    pub destination: Option<String>,
+    pub status: ItemStatus,
+    pub error: Option<OpError>,
+    #[serde(flatten)]
+    pub details: ItemDetails,
+}
+
+/// バッチ操作の結果（操作対象ごと）
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReport {
+    pub items: Vec<ItemResult>,
+}
+
+/// 途中で中断したバッチ操作。中断するまでに処理した項目の結果も持つ
+#[derive(Debug, Clone)]
+pub struct BatchFailure {
+    pub error: OpError,
+    /// 中断するまでの結果。済んだ項目を操作履歴に記録するのに使う
+    pub report: BatchReport,
+}
+
+impl From<OpError> for BatchFailure {
+    fn from(error: OpError) -> Self {
+        Self {
+            error,
+            report: BatchReport::default(),
+        }
+    }
+}
+
+impl From<BatchFailure> for String {
+    fn from(failure: BatchFailure) -> Self {
+        failure.error.message
+    }
+}
+
+impl From<BatchFailure> for OpError {
+    fn from(failure: BatchFailure) -> Self {
+        failure.error
+    }
+}
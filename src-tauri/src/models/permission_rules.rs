@@ -0,0 +1,15 @@
+use serde::Deserialize;
+
+/// set_permissions の指定。モードは `755` のような 8 進数か `u+x,go-w` のような記号表記
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PermissionRules {
+    /// ファイル・フォルダ共通のモード
+    pub mode: Option<String>,
+    /// ファイルだけに使うモード（`mode` より優先）
+    pub file_mode: Option<String>,
+    /// フォルダだけに使うモード（`mode` より優先）
+    pub dir_mode: Option<String>,
+    /// フォルダの中身にも適用する
+    pub recursive: bool,
+}
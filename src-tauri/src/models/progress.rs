@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+/// コピー・移動の進捗。`file_op_progress` イベントで送信する
+#[derive(Debug, Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferProgress {
+    /// フロントエンドが指定した操作 ID（イベントの突き合わせ用）
+    pub operation_id: Option<String>,
+    /// 処理中のファイル
+    pub current_file: String,
+    pub file_bytes_copied: u64,
+    pub file_bytes_total: u64,
+    /// バッチ全体の進捗（事前スキャンした合計に対する値）
+    pub bytes_copied: u64,
+    pub bytes_total: u64,
+    pub items_done: usize,
+    pub items_total: usize,
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+
+/// 一括名前変更のルール。適用順は 置換 → 接頭辞・接尾辞 → 大文字小文字 → 拡張子。
+/// 置換後の文字列・接頭辞・接尾辞では `{n}`（連番）と `{date}`・`{date:%Y%m%d}`（更新日時）が使える
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RenameRules {
+    pub find_replace: Option<FindReplace>,
+    pub prefix: String,
+    pub suffix: String,
+    pub counter: CounterRule,
+    pub case: Option<CaseConversion>,
+    /// 新しい拡張子（先頭の `.` は省略可、空文字で拡張子を外す）。未指定なら変更しない
+    pub extension: Option<String>,
+}
+
+/// 拡張子を除いた名前に対する検索・置換
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FindReplace {
+    pub find: String,
+    /// 正規表現では `$1`・`${name}` でキャプチャを参照できる
+    pub replace: String,
+    pub regex: bool,
+    pub case_sensitive: bool,
+}
+
+/// `{n}` に入る連番
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct CounterRule {
+    pub start: u64,
+    pub step: u64,
+    /// ゼロ埋めする桁数（32 桁まで）
+    pub padding: usize,
+}
+
+impl Default for CounterRule {
+    fn default() -> Self {
+        Self {
+            start: 1,
+            step: 1,
+            padding: 0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum CaseConversion {
+    Lower,
+    Upper,
+    /// 単語の先頭だけ大文字
+    Title,
+}
@@ -0,0 +1,59 @@
+use super::ConflictStrategy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// コピー・移動コマンドの追加オプション（省略時はすべて既定値）
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TransferOptions {
+    /// 失敗した項目があっても残りの処理を続ける
+    pub continue_on_error: bool,
+    /// 操作対象（コピー元パス）ごとの衝突解決。指定がなければ全体の方針に従う
+    pub decisions: HashMap<String, ConflictStrategy>,
+    /// 同名のフォルダがある場合に、中身を 1 ファイルずつ衝突解決しながら統合する
+    pub merge_directories: bool,
+    /// コピー先に引き継ぐメタデータ
+    pub preserve: PreserveOptions,
+    /// シンボリックリンクの扱い
+    pub symlinks: SymlinkPolicy,
+    /// ファイルごとに書き込み後のハッシュを比較する（移動では一致した場合だけ移動元を消す）
+    pub verify: bool,
+}
+
+/// コピー・移動時のシンボリックリンクの扱い
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    /// リンクそのものをコピーする
+    #[default]
+    CopyAsLink,
+    /// リンク先の実体をコピーする（ループや切れたリンクはリンクのままコピー）
+    Follow,
+    /// リンクはコピーしない
+    Skip,
+}
+
+/// コピー時に元ファイルから引き継ぐ属性。既定ではパーミッションのみ
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PreserveOptions {
+    /// 更新日時・アクセス日時
+    pub timestamps: bool,
+    /// パーミッション（Unix の mode、Windows の読み取り専用属性）
+    pub permissions: bool,
+    /// 所有者・グループ（権限がなければ黙って引き継がない）
+    pub ownership: bool,
+    /// 拡張属性
+    pub xattrs: bool,
+}
+
+impl Default for PreserveOptions {
+    fn default() -> Self {
+        Self {
+            timestamps: false,
+            permissions: true,
+            ownership: false,
+            xattrs: false,
+        }
+    }
+}
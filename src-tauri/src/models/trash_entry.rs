@@ -0,0 +1,18 @@
+use serde::Serialize;
+
+/// ゴミ箱内の項目
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    /// OS ごとの識別子（復元・完全削除で指定する）
+    pub id: String,
+    pub name: String,
+    pub original_path: String,
+    /// 削除日時（UNIX エポックからのミリ秒）。不明なら `None`
+    pub deleted_at: Option<i64>,
+    pub is_dir: bool,
+    /// ファイルのバイト数（フォルダは `None`）
+    pub size: Option<u64>,
+    /// フォルダ直下の項目数（ファイルは `None`）
+    pub item_count: Option<usize>,
+}
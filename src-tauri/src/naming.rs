@@ -0,0 +1,124 @@
+//! ファイル名の検証。Windows や FAT のボリュームへ持ち出せない名前も、OS を問わず拒否する
+
+use crate::models::{ErrorKind, OpError};
+use std::path::Path;
+
+/// NAME_MAX（バイト）と Windows の上限（UTF-16 の文字数）
+const MAX_NAME_LEN: usize = 255;
+
+const INVALID_CHARS: &[char] = &['<', '>', ':', '"', '|', '?', '*'];
+
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 新しく付けるファイル・フォルダ名として使えるか
+pub fn validate_file_name(name: &str) -> Result<(), OpError> {
+    if name.trim().is_empty() {
+        return Err(OpError::new(ErrorKind::InvalidInput, "名前を入力してください"));
+    }
+    if name.contains(['/', '\\']) {
+        return Err(OpError::new(
+            ErrorKind::PathSeparator,
+            format!("名前にパスの区切り文字は使用できません: {}", name),
+        ));
+    }
+    if let Some(c) = name.chars().find(|c| c.is_control() || INVALID_CHARS.contains(c)) {
+        return Err(OpError::new(
+            ErrorKind::InvalidCharacter,
+            format!("名前に使用できない文字が含まれています: {:?}", c),
+        ));
+    }
+    // Windows では末尾のピリオド・空白が黙って削られる
+    if name.ends_with(['.', ' ']) && name != "." && name != ".." {
+        return Err(OpError::new(
+            ErrorKind::InvalidCharacter,
+            format!("名前の末尾にピリオドや空白は使用できません: {}", name),
+        ));
+    }
+    if is_reserved(name) {
+        return Err(OpError::new(
+            ErrorKind::ReservedName,
+            format!("予約されている名前は使用できません: {}", name),
+        ));
+    }
+    if name.len() > MAX_NAME_LEN || name.encode_utf16().count() > MAX_NAME_LEN {
+        return Err(OpError::new(
+            ErrorKind::NameTooLong,
+            format!("名前が長すぎます（最大 {} 文字）", MAX_NAME_LEN),
+        ));
+    }
+    Ok(())
+}
+
+/// `.`・`..` と、拡張子を除いた部分が Windows のデバイス名と一致する名前
+fn is_reserved(name: &str) -> bool {
+    if name == "." || name == ".." {
+        return true;
+    }
+    let base = name.split('.').next().unwrap_or_default().trim_end();
+    RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(base))
+}
+
+/// 2 つのパスが同じ項目を指しているか（大文字小文字を区別しないファイルシステムで、
+/// 名前の大文字小文字だけを変える場合の判定に使う）
+#[cfg(unix)]
+pub fn is_same_entry(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (a.symlink_metadata(), b.symlink_metadata()) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub fn is_same_entry(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(name: &str) -> Option<ErrorKind> {
+        validate_file_name(name).err().map(|e| e.kind)
+    }
+
+    #[test]
+    fn accepts_ordinary_names() {
+        for name in ["readme.md", "写真 2024.jpg", ".gitignore", "a.b.c", "CONFIG.sys"] {
+            assert_eq!(kind(name), None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_names_with_typed_errors() {
+        assert_eq!(kind(""), Some(ErrorKind::InvalidInput));
+        assert_eq!(kind("a/b"), Some(ErrorKind::PathSeparator));
+        assert_eq!(kind("..\\x"), Some(ErrorKind::PathSeparator));
+        assert_eq!(kind("what?.txt"), Some(ErrorKind::InvalidCharacter));
+        assert_eq!(kind("tab\there"), Some(ErrorKind::InvalidCharacter));
+        assert_eq!(kind("trailing."), Some(ErrorKind::InvalidCharacter));
+        assert_eq!(kind(".."), Some(ErrorKind::ReservedName));
+        assert_eq!(kind("con"), Some(ErrorKind::ReservedName));
+        assert_eq!(kind("LPT1.txt"), Some(ErrorKind::ReservedName));
+        assert_eq!(kind(&"a".repeat(256)), Some(ErrorKind::NameTooLong));
+        // UTF-8 で 255 バイトを超える
+        assert_eq!(kind(&"あ".repeat(100)), Some(ErrorKind::NameTooLong));
+    }
+
+    #[test]
+    fn same_entry_detects_identical_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.txt");
+        let b = dir.path().join("b.txt");
+        std::fs::write(&a, "a").unwrap();
+        std::fs::write(&b, "b").unwrap();
+        assert!(is_same_entry(&a, &dir.path().join(".").join("a.txt")));
+        assert!(!is_same_entry(&a, &b));
+    }
+}
@@ -0,0 +1,156 @@
+//! ファイル名の自然順比較。数字の部分は数値として比べる（`file2` < `file10`）。
+//! macOS の共有フォルダなどから来る NFD の名前も NFC に揃えてから比べる
+
+use std::cmp::Ordering;
+use unicode_normalization::UnicodeNormalization;
+
+/// 比較用に正規化した文字列。大文字小文字を区別せず、`fold_kana` なら
+/// ひらがなとカタカナ、全角と半角も区別しない。先に作っておき、`compare_folded` で比べる
+pub fn fold(s: &str, fold_kana: bool) -> String {
+    if fold_kana {
+        // NFKC で全角英数字と半角カナを揃えてから、カタカナをひらがなにする
+        s.nfkc()
+            .map(katakana_to_hiragana)
+            .collect::<String>()
+            .to_lowercase()
+    } else {
+        s.nfc().collect::<String>().to_lowercase()
+    }
+}
+
+fn katakana_to_hiragana(c: char) -> char {
+    match c {
+        'ァ'..='ヶ' | 'ヽ' | 'ヾ' => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// `fold` した文字列どうしを自然順で比べる
+pub fn compare_folded(a: &str, b: &str) -> Ordering {
+    let mut a = Runs::new(a);
+    let mut b = Runs::new(b);
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => {
+                let ord = match (is_number(x), is_number(y)) {
+                    (true, true) => compare_numbers(x, y),
+                    _ => x.cmp(y),
+                };
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+        }
+    }
+}
+
+fn is_number(run: &str) -> bool {
+    run.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// 桁数の多い方が大きい。同じ値なら先頭のゼロが少ない方を前にする
+fn compare_numbers(a: &str, b: &str) -> Ordering {
+    let a_digits = a.trim_start_matches('0');
+    let b_digits = b.trim_start_matches('0');
+    a_digits
+        .len()
+        .cmp(&b_digits.len())
+        .then_with(|| a_digits.cmp(b_digits))
+        .then_with(|| a.len().cmp(&b.len()))
+}
+
+/// 数字の連続とそれ以外の連続に分ける
+struct Runs<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Runs<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { rest: s }
+    }
+}
+
+impl<'a> Iterator for Runs<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let first = self.rest.chars().next()?;
+        let digit = first.is_ascii_digit();
+        let end = self
+            .rest
+            .find(|c: char| c.is_ascii_digit() != digit)
+            .unwrap_or(self.rest.len());
+        let (run, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Some(run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn natural_cmp(a: &str, b: &str) -> Ordering {
+        natural_cmp_with(a, b, false)
+    }
+
+    fn natural_cmp_with(a: &str, b: &str, fold_kana: bool) -> Ordering {
+        compare_folded(&fold(a, fold_kana), &fold(b, fold_kana)).then_with(|| a.cmp(b))
+    }
+
+    fn sorted(names: &[&str]) -> Vec<String> {
+        let mut names: Vec<String> = names.iter().map(|s| s.to_string()).collect();
+        names.sort_by(|a, b| natural_cmp(a, b));
+        names
+    }
+
+    #[test]
+    fn numbers_are_compared_by_value() {
+        assert_eq!(
+            sorted(&["file10.txt", "file2.txt", "file1.txt", "File3.txt"]),
+            ["file1.txt", "file2.txt", "File3.txt", "file10.txt"]
+        );
+        assert_eq!(
+            sorted(&["v1.10", "v1.9", "v1.2"]),
+            ["v1.2", "v1.9", "v1.10"]
+        );
+    }
+
+    #[test]
+    fn ties_are_broken_deterministically() {
+        assert_eq!(sorted(&["a02", "a2", "a002"]), ["a2", "a02", "a002"]);
+        assert_eq!(natural_cmp("ABC", "abc"), "ABC".cmp("abc"));
+        assert_eq!(natural_cmp("a", "a1"), Ordering::Less);
+    }
+
+    #[test]
+    fn nfd_names_sort_with_their_nfc_forms() {
+        // 「が」の NFC と NFD（か + 濁点）
+        let nfc = "が2.txt";
+        let nfd = "か\u{3099}10.txt";
+        assert_eq!(natural_cmp(nfc, nfd), Ordering::Less);
+        assert_eq!(natural_cmp("き.txt", nfd), Ordering::Greater);
+        assert_eq!(fold(nfd, false), fold("が10.txt", false));
+    }
+
+    #[test]
+    fn kana_and_width_are_folded_only_when_asked() {
+        assert_ne!(
+            compare_folded(&fold("カ", false), &fold("か", false)),
+            Ordering::Equal
+        );
+        assert_eq!(fold("カタカナ", true), fold("かたかな", true));
+        assert_eq!(fold("ｶﾞｲﾄﾞ", true), fold("ガイド", true));
+        assert_eq!(
+            natural_cmp_with("ＦＩＬＥ２", "file10", true),
+            Ordering::Less
+        );
+        assert_eq!(
+            natural_cmp_with("ＦＩＬＥ２", "file10", false),
+            Ordering::Greater
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! chmod のモード指定（`755` のような 8 進数と `u+x,go-w` のような記号表記）
+
+use crate::models::{ErrorKind, OpError};
+
+/// 解析済みのモード指定
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModeSpec {
+    Octal(u32),
+    Symbolic(Vec<Clause>),
+}
+
+/// `go-w` のような 1 つの指定。`who` が空なら全員（umask は考慮しない）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clause {
+    who: Vec<char>,
+    actions: Vec<(char, Vec<char>)>,
+}
+
+impl ModeSpec {
+    pub fn parse(spec: &str) -> Result<Self, OpError> {
+        let spec = spec.trim();
+        let invalid = || {
+            OpError::new(
+                ErrorKind::InvalidInput,
+                format!("パーミッションの指定が正しくありません: {}", spec),
+            )
+        };
+        if spec.is_empty() {
+            return Err(invalid());
+        }
+        if spec.chars().all(|c| c.is_ascii_digit()) {
+            return match u32::from_str_radix(spec, 8) {
+                Ok(mode) if spec.len() <= 4 => Ok(Self::Octal(mode)),
+                _ => Err(invalid()),
+            };
+        }
+
+        let mut clauses = Vec::new();
+        for part in spec.split(',') {
+            let who: Vec<char> = part.chars().take_while(|c| "ugoa".contains(*c)).collect();
+            let mut rest = part[who.len()..].chars().peekable();
+            let mut actions = Vec::new();
+            while let Some(op) = rest.next() {
+                if !"+-=".contains(op) {
+                    return Err(invalid());
+                }
+                let mut perms = Vec::new();
+                while let Some(&c) = rest.peek() {
+                    if "+-=".contains(c) {
+                        break;
+                    }
+                    if !"rwxXst".contains(c) {
+                        return Err(invalid());
+                    }
+                    perms.push(c);
+                    rest.next();
+                }
+                actions.push((op, perms));
+            }
+            if actions.is_empty() {
+                return Err(invalid());
+            }
+            clauses.push(Clause { who, actions });
+        }
+        Ok(Self::Symbolic(clauses))
+    }
+
+    /// 現在のモードにこの指定を適用した結果（下位 12 ビット）
+    pub fn apply(&self, current: u32, is_dir: bool) -> u32 {
+        let clauses = match self {
+            Self::Octal(mode) => return *mode & 0o7777,
+            Self::Symbolic(clauses) => clauses,
+        };
+        let mut mode = current & 0o7777;
+        for clause in clauses {
+            let who_mask = who_mask(&clause.who);
+            for (op, perms) in &clause.actions {
+                let bits = perm_bits(perms, mode, is_dir) & who_mask;
+                match op {
+                    '+' => mode |= bits,
+                    '-' => mode &= !bits,
+                    _ => mode = (mode & !who_mask) | bits,
+                }
+            }
+        }
+        mode
+    }
+}
+
+/// 対象者が変更できるビット（特殊ビットを含む）
+fn who_mask(who: &[char]) -> u32 {
+    if who.is_empty() || who.contains(&'a') {
+        return 0o7777;
+    }
+    who.iter()
+        .map(|w| match w {
+            'u' => 0o4700,
+            'g' => 0o2070,
+            _ => 0o1007,
+        })
+        .fold(0, |acc, bits| acc | bits)
+}
+
+/// 全員分のビットを立てたもの。対象者のマスクと組み合わせて使う
+fn perm_bits(perms: &[char], current: u32, is_dir: bool) -> u32 {
+    perms
+        .iter()
+        .map(|p| match p {
+            'r' => 0o444,
+            'w' => 0o222,
+            'x' => 0o111,
+            // フォルダか、既に誰かが実行できるファイルだけ実行可能にする
+            'X' if is_dir || current & 0o111 != 0 => 0o111,
+            's' => 0o6000,
+            't' => 0o1000,
+            _ => 0,
+        })
+        .fold(0, |acc, bits| acc | bits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(spec: &str, current: u32, is_dir: bool) -> u32 {
+        ModeSpec::parse(spec).unwrap().apply(current, is_dir)
+    }
+
+    #[test]
+    fn octal_modes_replace_the_current_mode() {
+        assert_eq!(apply("755", 0o600, false), 0o755);
+        assert_eq!(apply("0640", 0o777, false), 0o640);
+        assert_eq!(apply("4755", 0o644, false), 0o4755);
+    }
+
+    #[test]
+    fn symbolic_modes_adjust_the_current_mode() {
+        assert_eq!(apply("u+x", 0o644, false), 0o744);
+        assert_eq!(apply("go-w", 0o666, false), 0o644);
+        assert_eq!(apply("a=r", 0o755, false), 0o444);
+        assert_eq!(apply("+x", 0o644, false), 0o755);
+        assert_eq!(apply("u=rw,g=r,o=", 0o777, false), 0o640);
+        assert_eq!(apply("u+r-w", 0o200, false), 0o400);
+        assert_eq!(apply("g+s,o+t", 0o755, true), 0o3755);
+    }
+
+    #[test]
+    fn capital_x_only_applies_to_dirs_and_executables() {
+        assert_eq!(apply("a+X", 0o644, false), 0o644);
+        assert_eq!(apply("a+X", 0o644, true), 0o755);
+        assert_eq!(apply("a+X", 0o744, false), 0o755);
+    }
+
+    #[test]
+    fn rejects_malformed_specs() {
+        for spec in ["", "8", "77777", "u", "u+q", "z+x", "u+x,"] {
+            let err = ModeSpec::parse(spec).unwrap_err();
+            assert_eq!(err.kind, ErrorKind::InvalidInput, "{}", spec);
+        }
+    }
+}
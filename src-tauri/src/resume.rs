@@ -0,0 +1,452 @@
+//! 中断したコピージョブを再開するためのジャーナル。
+//! 1 行 1 レコードの JSON で、ジョブの内容・実行計画・ファイルごとの開始・コピー先の作り直し・完了を追記していく。
+//! ジョブが終了すると削除されるので、起動時に残っているものはアプリの終了やクラッシュで中断したジョブ。
+
+use crate::models::{ConflictStrategy, SymlinkPolicy, TransferOptions};
+use crate::transfer::{PlannedItem, PlannedTransfer};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, Metadata, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// アプリのデータフォルダ内でジャーナルを置くフォルダ名
+pub const JOURNAL_DIR: &str = "job-journal";
+
+const JOURNAL_EXT: &str = "jsonl";
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Record {
+    /// 先頭行。ジョブとして受け付けた内容
+    Job {
+        sources: Vec<String>,
+        destination: String,
+        strategy: Option<String>,
+        options: TransferOptions,
+    },
+    /// 衝突解決済みの実行計画（1 項目 1 行）
+    Item {
+        src: PathBuf,
+        dest: PathBuf,
+        merge: Option<ConflictStrategy>,
+        /// 再開時にはコピー先が途中まで書かれているので、計画時の値を残しておく
+        #[serde(default)]
+        creates: bool,
+    },
+    Started {
+        path: PathBuf,
+        /// 開始時のコピー元。古いジャーナルにはない
+        #[serde(default)]
+        source: Option<SourceStamp>,
+    },
+    /// コピー先を作り直した。以降コピー先にあるのはこのジョブが書いた内容だけ
+    Truncated {
+        path: PathBuf,
+    },
+    Done {
+        path: PathBuf,
+    },
+}
+
+/// コピー元が記録した時点から変わっていないかを見るためのサイズと更新日時
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+struct SourceStamp {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl SourceStamp {
+    fn of(meta: &Metadata) -> Self {
+        Self {
+            len: meta.len(),
+            modified: meta.modified().ok(),
+        }
+    }
+}
+
+/// 起動時に見つかった中断済みジョブ（フロントエンドに返す）
+#[derive(Debug, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InterruptedJob {
+    pub id: String,
+    pub sources: Vec<String>,
+    pub destination: String,
+    pub strategy: Option<String>,
+    /// 完了済みのファイル数
+    pub files_done: usize,
+    #[serde(skip)]
+    pub options: TransferOptions,
+}
+
+/// 前回の実行で記録された内容
+#[derive(Debug, Default)]
+struct PreviousRun {
+    plan: Vec<(PathBuf, PathBuf, Option<ConflictStrategy>, bool)>,
+    /// 手を付けたファイルと、最後に開始したときのコピー元
+    started: HashMap<PathBuf, Option<SourceStamp>>,
+    truncated: HashSet<PathBuf>,
+    done: HashSet<PathBuf>,
+}
+
+pub struct Journal {
+    file: File,
+    previous: Option<PreviousRun>,
+}
+
+impl Journal {
+    /// 新しいジョブのジャーナルを作る
+    pub fn create(
+        path: &Path,
+        sources: &[String],
+        destination: &str,
+        strategy: Option<&str>,
+        options: &TransferOptions,
+    ) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut journal = Self {
+            file: File::create(path)?,
+            previous: None,
+        };
+        journal.append(&Record::Job {
+            sources: sources.to_vec(),
+            destination: destination.to_string(),
+            strategy: strategy.map(str::to_string),
+            options: options.clone(),
+        });
+        Ok(journal)
+    }
+
+    /// 中断したジョブのジャーナルを読み込み、続きを追記できるように開く
+    pub fn reopen(path: &Path) -> std::io::Result<Self> {
+        let (_, previous) = read_journal(path)?;
+        Ok(Self {
+            file: OpenOptions::new().append(true).open(path)?,
+            previous: Some(previous),
+        })
+    }
+
+    /// 前回の実行計画。計画を記録する前に中断していた場合は `None`
+    pub fn previous_plan(&self, symlinks: SymlinkPolicy) -> Option<Vec<PlannedItem>> {
+        let previous = self.previous.as_ref()?;
+        if previous.plan.is_empty() {
+            return None;
+        }
+        let plan = previous.plan.iter().map(|(src, dest, merge, creates)| {
+            let mut transfer = PlannedTransfer::new(src, dest.clone(), symlinks);
+            transfer.creates = *creates;
+            PlannedItem::Transfer(match merge {
+                Some(strategy) => transfer.merging(*strategy),
+                None => transfer,
+            })
+        });
+        Some(plan.collect())
+    }
+
+    /// 実行計画を記録する（再開時は前回の計画をそのまま使うので記録しない）
+    pub fn record_plan(&mut self, plan: &[PlannedItem]) {
+        if self.previous.is_some() {
+            return;
+        }
+        for item in plan {
+            if let PlannedItem::Transfer(t) = item {
+                self.append(&Record::Item {
+                    src: t.src.clone(),
+                    dest: t.dest.clone(),
+                    merge: t.merge,
+                    creates: t.creates,
+                });
+            }
+        }
+    }
+
+    pub fn record_started(&mut self, src: &Path, src_meta: &Metadata) {
+        self.append(&Record::Started {
+            path: src.to_path_buf(),
+            source: Some(SourceStamp::of(src_meta)),
+        });
+    }
+
+    pub fn record_truncated(&mut self, src: &Path) {
+        self.append(&Record::Truncated {
+            path: src.to_path_buf(),
+        });
+    }
+
+    pub fn record_done(&mut self, src: &Path) {
+        self.append(&Record::Done {
+            path: src.to_path_buf(),
+        });
+    }
+
+    /// 前回の実行で手を付けたファイルか（マージ時に衝突として扱わない）
+    pub fn touched(&self, src: &Path) -> bool {
+        self.previous
+            .as_ref()
+            .is_some_and(|p| p.started.contains_key(src) || p.done.contains(src))
+    }
+
+    /// 再開時にコピー先の既存ファイルをどこまで使えるか。
+    /// サイズと更新日時がコピー先と一致するか、前回完了したなら全体、
+    /// 前回書きかけだったファイルなら書き込み済みの長さを返す。
+    /// コピー先を作り直す前に中断していた場合、中身は以前からあった別のファイルなので使わない。
+    /// 前回の実行後にコピー元が変わっていれば、前回の記録は使わずに最初からコピーし直す
+    pub fn resume_offset(&self, src: &Path, src_meta: &Metadata, dst: &Path) -> Option<u64> {
+        let previous = self.previous.as_ref()?;
+        let dst_meta = dst.symlink_metadata().ok().filter(|m| m.is_file())?;
+        let (src_len, dst_len) = (src_meta.len(), dst_meta.len());
+
+        let same_mtime = src_meta.modified().ok() == dst_meta.modified().ok();
+        let unchanged =
+            previous.started.get(src).copied().flatten() == Some(SourceStamp::of(src_meta));
+        let done = unchanged && previous.done.contains(src);
+        if src_len == dst_len && (done || same_mtime) {
+            return Some(src_len);
+        }
+        if unchanged && previous.truncated.contains(src) && !done && dst_len < src_len {
+            return Some(dst_len);
+        }
+        None
+    }
+
+    // ジャーナルが書けなくてもコピー自体は続ける
+    fn append(&mut self, record: &Record) {
+        if let Ok(line) = serde_json::to_string(record) {
+            let _ = writeln!(self.file, "{}", line);
+        }
+    }
+}
+
+fn read_journal(path: &Path) -> std::io::Result<(Option<InterruptedJob>, PreviousRun)> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut job = None;
+    let mut previous = PreviousRun::default();
+    // 最後の行はクラッシュで途中までしか書かれていないことがあるので、読めない行は無視する
+    for line in reader.lines() {
+        let Ok(record) = serde_json::from_str::<Record>(&line?) else {
+            continue;
+        };
+        match record {
+            Record::Job {
+                sources,
+                destination,
+                strategy,
+                options,
+            } => {
+                job = Some(InterruptedJob {
+                    id: journal_id(path),
+                    sources,
+                    destination,
+                    strategy,
+                    files_done: 0,
+                    options,
+                });
+            }
+            Record::Item {
+                src,
+                dest,
+                merge,
+                creates,
+            } => previous.plan.push((src, dest, merge, creates)),
+            Record::Started { path, source } => {
+                previous.started.insert(path, source);
+            }
+            Record::Truncated { path } => {
+                previous.truncated.insert(path);
+            }
+            Record::Done { path } => {
+                previous.done.insert(path);
+            }
+        }
+    }
+    if let Some(job) = job.as_mut() {
+        job.files_done = previous.done.len();
+    }
+    Ok((job, previous))
+}
+
+fn journal_id(path: &Path) -> String {
+    path.file_stem().unwrap_or_default().to_string_lossy().to_string()
+}
+
+/// 新しいジャーナルのパス
+pub fn new_journal_path(dir: &Path) -> PathBuf {
+    let stamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or_default();
+    let path = dir.join(format!("{}.{}", stamp, JOURNAL_EXT));
+    if path.exists() {
+        crate::transfer::generate_unique_name(&path)
+    } else {
+        path
+    }
+}
+
+pub fn journal_path(dir: &Path, id: &str) -> Result<PathBuf, String> {
+    // id はファイル名だけを受け付ける
+    if id.is_empty() || Path::new(id).file_name() != Some(std::ffi::OsStr::new(id)) {
+        return Err(format!("不正なジャーナル ID: {}", id));
+    }
+    Ok(dir.join(format!("{}.{}", id, JOURNAL_EXT)))
+}
+
+/// フォルダ内の中断済みジョブを古い順に返す。`in_use` のジャーナルは実行中なので除く
+pub fn list_interrupted(dir: &Path, in_use: &[PathBuf]) -> Vec<InterruptedJob> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|ext| ext == JOURNAL_EXT))
+        .filter(|p| !in_use.contains(p))
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .filter_map(|p| read_journal(p).ok().and_then(|(job, _)| job))
+        .collect()
+}
+
+pub fn read_interrupted(path: &Path) -> Result<InterruptedJob, String> {
+    read_journal(path)
+        .ok()
+        .and_then(|(job, _)| job)
+        .ok_or_else(|| format!("中断したジョブが見つかりません: {}", journal_id(path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn start(journal: &mut Journal, src: &Path) {
+        journal.record_started(src, &fs::metadata(src).unwrap());
+    }
+
+    fn journal_with_run(dir: &Path, src: &Path, dest: &Path) -> PathBuf {
+        let path = new_journal_path(dir);
+        let mut journal = Journal::create(
+            &path,
+            &[src.to_string_lossy().to_string()],
+            &dest.to_string_lossy(),
+            None,
+            &TransferOptions::default(),
+        )
+        .unwrap();
+        journal.record_plan(&[PlannedItem::Transfer(PlannedTransfer::new(
+            src,
+            dest.join("src"),
+            SymlinkPolicy::CopyAsLink,
+        ))]);
+        start(&mut journal, &src.join("done.txt"));
+        journal.record_done(&src.join("done.txt"));
+        start(&mut journal, &src.join("partial.bin"));
+        journal.record_truncated(&src.join("partial.bin"));
+        // コピー先を作り直す前に中断した（古いファイルが残っている）
+        start(&mut journal, &src.join("stale.bin"));
+        path
+    }
+
+    #[test]
+    fn reopen_restores_plan_and_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(dest.join("src")).unwrap();
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("done.txt"), "done").unwrap();
+        fs::write(src.join("partial.bin"), "0123456789").unwrap();
+        fs::write(dest.join("src").join("done.txt"), "done").unwrap();
+        fs::write(dest.join("src").join("partial.bin"), "0123").unwrap();
+        fs::write(src.join("stale.bin"), "new contents").unwrap();
+        fs::write(dest.join("src").join("stale.bin"), "old").unwrap();
+
+        let journal_dir = dir.path().join(JOURNAL_DIR);
+        let path = journal_with_run(&journal_dir, &src, &dest);
+
+        let jobs = list_interrupted(&journal_dir, &[]);
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].files_done, 1);
+        assert!(list_interrupted(&journal_dir, std::slice::from_ref(&path)).is_empty());
+
+        let journal = Journal::reopen(&path).unwrap();
+        let plan = journal.previous_plan(SymlinkPolicy::CopyAsLink).unwrap();
+        assert_eq!(plan.len(), 1);
+        let offset = |name: &str| {
+            let src_file = src.join(name);
+            let meta = fs::metadata(&src_file).unwrap();
+            journal.resume_offset(&src_file, &meta, &dest.join("src").join(name))
+        };
+        assert_eq!(offset("done.txt"), Some(4));
+        assert_eq!(offset("partial.bin"), Some(4));
+        assert_eq!(offset("stale.bin"), None);
+    }
+
+    #[test]
+    fn changed_source_is_copied_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dest = dir.path().join("dest");
+        fs::create_dir_all(dest.join("src")).unwrap();
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("done.txt"), "done").unwrap();
+        fs::write(src.join("partial.bin"), "0123456789").unwrap();
+        fs::write(src.join("stale.bin"), "stale").unwrap();
+        fs::write(dest.join("src").join("done.txt"), "done").unwrap();
+        fs::write(dest.join("src").join("partial.bin"), "0123").unwrap();
+        let path = journal_with_run(dir.path(), &src, &dest);
+
+        // 中断後に同じサイズのまま書き換えられた
+        let edited = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_000_000);
+        for (name, contents) in [("done.txt", "DONE"), ("partial.bin", "9876543210")] {
+            fs::write(src.join(name), contents).unwrap();
+            File::options()
+                .write(true)
+                .open(src.join(name))
+                .unwrap()
+                .set_modified(edited)
+                .unwrap();
+        }
+
+        let journal = Journal::reopen(&path).unwrap();
+        for name in ["done.txt", "partial.bin"] {
+            let src_file = src.join(name);
+            let meta = fs::metadata(&src_file).unwrap();
+            let offset = journal.resume_offset(&src_file, &meta, &dest.join("src").join(name));
+            assert_eq!(offset, None, "{}", name);
+        }
+    }
+
+    #[test]
+    fn torn_last_line_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        for name in ["done.txt", "partial.bin", "stale.bin"] {
+            fs::write(src.join(name), name).unwrap();
+        }
+        let path = journal_with_run(dir.path(), &src, dir.path());
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        write!(file, "{{\"done\":{{\"pa").unwrap();
+
+        let job = read_interrupted(&path).unwrap();
+        assert_eq!(job.files_done, 1);
+    }
+
+    #[test]
+    fn journal_path_rejects_traversal() {
+        let dir = Path::new("/journals");
+        assert!(journal_path(dir, "../etc/passwd").is_err());
+        assert!(journal_path(dir, "").is_err());
+        assert_eq!(
+            journal_path(dir, "123").unwrap(),
+            Path::new("/journals/123.jsonl")
+        );
+    }
+}
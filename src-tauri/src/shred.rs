@@ -0,0 +1,103 @@
+//! ファイルの中身を上書きしてから削除する（シュレッド）。
+//! SSD のウェアレベリングやコピーオンライトのファイルシステムでは、
+//! 元のデータが物理的に消えることまでは保証できない。
+
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::SystemTime;
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+
+/// ファイル・フォルダ内のファイルをすべて上書きしてから削除する。リンクはたどらずに削除する
+pub fn shred_path(path: &Path) -> io::Result<()> {
+    let metadata = path.symlink_metadata()?;
+    if metadata.is_dir() {
+        for entry in walkdir::WalkDir::new(path) {
+            let entry = entry?;
+            if entry.file_type().is_file() {
+                overwrite_file(entry.path())?;
+            }
+        }
+        std::fs::remove_dir_all(path)
+    } else {
+        if metadata.is_file() {
+            overwrite_file(path)?;
+        }
+        std::fs::remove_file(path)
+    }
+}
+
+/// 中身を同じ長さの疑似乱数で上書きし、ディスクへ書き出す
+fn overwrite_file(path: &Path) -> io::Result<()> {
+    let mut file = OpenOptions::new().write(true).open(path)?;
+    let len = file.metadata()?.len();
+
+    // 推測されにくいように、パスと時刻から作った鍵で BLAKE3 の出力を乱数として使う
+    let nanos = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(path.as_os_str().as_encoded_bytes());
+    hasher.update(&nanos.to_le_bytes());
+    let mut noise = hasher.finalize_xof();
+
+    let mut buf = vec![0u8; CHUNK_SIZE];
+    let mut remaining = len;
+    while remaining > 0 {
+        let n = remaining.min(CHUNK_SIZE as u64) as usize;
+        noise.fill(&mut buf[..n]);
+        file.write_all(&buf[..n])?;
+        remaining -= n as u64;
+    }
+    file.sync_all()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn overwrite_replaces_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret.txt");
+        let secret = b"top secret ".repeat(1000);
+        fs::write(&path, &secret).unwrap();
+
+        // 削除前に同じ inode を開いておき、上書きされたことを確かめる
+        let link = dir.path().join("link.txt");
+        fs::hard_link(&path, &link).unwrap();
+        overwrite_file(&path).unwrap();
+        let contents = fs::read(&link).unwrap();
+        assert_eq!(contents.len(), secret.len());
+        assert!(contents.windows(10).all(|w| w != b"top secret"));
+    }
+
+    #[test]
+    fn shred_removes_directory_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("secrets");
+        fs::create_dir_all(root.join("nested")).unwrap();
+        fs::write(root.join("a.txt"), "a").unwrap();
+        fs::write(root.join("nested").join("b.txt"), "b").unwrap();
+
+        shred_path(&root).unwrap();
+        assert!(!root.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn shred_does_not_follow_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("keep.txt");
+        fs::write(&target, "keep").unwrap();
+        let root = dir.path().join("folder");
+        fs::create_dir(&root).unwrap();
+        std::os::unix::fs::symlink(&target, root.join("link")).unwrap();
+
+        shred_path(&root).unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "keep");
+    }
+}
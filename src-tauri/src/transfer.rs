@@ -0,0 +1,1509 @@
+use crate::fastcopy;
+use crate::resume::Journal;
+use crate::models::{
+    BatchFailure, BatchReport, ConflictStrategy, CopyMethod, ErrorKind, ItemDetails, ItemResult,
+    ItemState, ItemStatus,
+    NestedConflict, OpError, PreserveOptions, Resolution, SymlinkPolicy, TransferOptions,
+    TransferProgress,
+};
+use std::collections::BTreeSet;
+use std::fs::{File, Metadata, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+/// 進捗イベント名
+pub const PROGRESS_EVENT: &str = "file_op_progress";
+
+const BUFFER_SIZE: usize = 1024 * 1024;
+/// イベントの送信間隔（小さいファイルが大量にある場合に IPC を溢れさせない）
+const EMIT_INTERVAL: Duration = Duration::from_millis(100);
+
+pub const CANCELLED_MESSAGE: &str = "キャンセルされました";
+
+type ProgressCallback = Box<dyn FnMut(&TransferProgress) + Send>;
+
+/// ジョブの一時停止・キャンセル要求。コピー処理はチャンクごとに確認する
+#[derive(Default)]
+pub struct JobControl {
+    cancelled: AtomicBool,
+    paused: Mutex<bool>,
+    resumed: Condvar,
+}
+
+impl JobControl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        // 一時停止中のスレッドも起こしてキャンセルを伝える
+        let _guard = self.paused.lock().unwrap();
+        self.resumed.notify_all();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    pub fn pause(&self) {
+        *self.paused.lock().unwrap() = true;
+    }
+
+    pub fn resume(&self) {
+        *self.paused.lock().unwrap() = false;
+        self.resumed.notify_all();
+    }
+
+    /// 一時停止中は再開まで待機し、キャンセル済みならエラーを返す
+    pub fn checkpoint(&self) -> Result<(), OpError> {
+        let mut paused = self.paused.lock().unwrap();
+        while *paused && !self.is_cancelled() {
+            paused = self.resumed.wait(paused).unwrap();
+        }
+        if self.is_cancelled() {
+            return Err(OpError::new(ErrorKind::Cancelled, CANCELLED_MESSAGE));
+        }
+        Ok(())
+    }
+}
+
+/// バッチ全体のバイト数・ファイル数を集計し、コールバックへ進捗を通知する
+pub struct ProgressTracker {
+    state: TransferProgress,
+    last_emit: Option<Instant>,
+    callback: Option<ProgressCallback>,
+    control: Option<Arc<JobControl>>,
+    /// 処理中の項目で使ったコピー方法
+    copy_methods: BTreeSet<CopyMethod>,
+    /// 中断時に再開できるよう、ファイルごとの進み具合を記録する
+    journal: Option<Journal>,
+}
+
+impl ProgressTracker {
+    pub fn new(operation_id: Option<String>, callback: ProgressCallback) -> Self {
+        Self {
+            state: TransferProgress {
+                operation_id,
+                ..Default::default()
+            },
+            last_emit: None,
+            callback: Some(callback),
+            control: None,
+            copy_methods: BTreeSet::new(),
+            journal: None,
+        }
+    }
+
+    /// 通知なし（テスト・内部処理用）
+    pub fn silent() -> Self {
+        Self {
+            state: TransferProgress::default(),
+            last_emit: None,
+            callback: None,
+            control: None,
+            copy_methods: BTreeSet::new(),
+            journal: None,
+        }
+    }
+
+    /// Tauri event として進捗を送信する
+    pub fn for_app(app: &AppHandle, operation_id: Option<String>) -> Self {
+        let app = app.clone();
+        Self::new(
+            operation_id,
+            Box::new(move |progress| {
+                let _ = app.emit(PROGRESS_EVENT, progress.clone());
+            }),
+        )
+    }
+
+    /// ジョブとして実行する場合に一時停止・キャンセルを受け付ける
+    pub fn with_control(mut self, control: Arc<JobControl>) -> Self {
+        self.control = Some(control);
+        self
+    }
+
+    /// ジャーナルに進み具合を記録し、再開時はそれを参照する
+    pub fn with_journal(mut self, journal: Journal) -> Self {
+        self.journal = Some(journal);
+        self
+    }
+
+    /// 一時停止・キャンセル要求を確認する
+    pub fn checkpoint(&self) -> Result<(), OpError> {
+        match &self.control {
+            Some(control) => control.checkpoint(),
+            None => Ok(()),
+        }
+    }
+
+    /// 事前スキャンの結果を合計に加算
+    pub fn add_total(&mut self, totals: ScanTotals) {
+        self.state.bytes_total += totals.bytes;
+        self.state.items_total += totals.files;
+    }
+
+    fn begin_file(&mut self, path: &Path, metadata: &Metadata) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.record_started(path, metadata);
+        }
+        self.state.current_file = path.to_string_lossy().to_string();
+        self.state.file_bytes_copied = 0;
+        self.state.file_bytes_total = metadata.len();
+        self.emit(false);
+    }
+
+    /// コピー先を作り直した後に呼ぶ。これより前に中断した場合は、再開時に続きから書かない
+    fn destination_truncated(&mut self, path: &Path) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.record_truncated(path);
+        }
+    }
+
+    fn advance(&mut self, bytes: u64) {
+        self.state.file_bytes_copied += bytes;
+        self.state.bytes_copied += bytes;
+        self.emit(false);
+    }
+
+    fn finish_file(&mut self, path: &Path) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.record_done(path);
+        }
+        self.state.items_done += 1;
+        self.emit(false);
+    }
+
+    /// rename で一括移動できた項目など、コピーせずに完了した分を進める
+    pub fn complete_without_copy(&mut self, path: &Path, totals: ScanTotals) {
+        self.state.current_file = path.to_string_lossy().to_string();
+        self.state.file_bytes_copied = totals.bytes;
+        self.state.file_bytes_total = totals.bytes;
+        self.state.bytes_copied += totals.bytes;
+        self.state.items_done += totals.files;
+        self.emit(false);
+    }
+
+    /// 前回の呼び出し以降に使ったコピー方法を取り出す
+    pub fn take_copy_methods(&mut self) -> Vec<CopyMethod> {
+        std::mem::take(&mut self.copy_methods).into_iter().collect()
+    }
+
+    /// 最終状態を必ず送信する
+    pub fn finish(&mut self) {
+        self.emit(true);
+    }
+
+    fn emit(&mut self, force: bool) {
+        let Some(callback) = self.callback.as_mut() else {
+            return;
+        };
+        let now = Instant::now();
+        let due = self
+            .last_emit
+            .is_none_or(|last| now.duration_since(last) >= EMIT_INTERVAL);
+        if force || due {
+            self.last_emit = Some(now);
+            callback(&self.state);
+        }
+    }
+}
+
+/// 事前スキャンの集計結果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScanTotals {
+    pub bytes: u64,
+    pub files: usize,
+}
+
+/// リンクとしてコピー（またはスキップ）する 1 項目分
+const LINK_TOTALS: ScanTotals = ScanTotals { bytes: 0, files: 1 };
+
+/// コピー対象のファイル数と合計サイズを数える。
+/// リンクをたどらない場合、リンクは 0 バイトの 1 項目として数える
+pub fn scan_path(path: &Path, symlinks: SymlinkPolicy) -> ScanTotals {
+    let follow = symlinks == SymlinkPolicy::Follow;
+    let mut totals = ScanTotals::default();
+    for entry in walkdir::WalkDir::new(path)
+        .follow_links(follow)
+        .follow_root_links(follow)
+    {
+        match entry {
+            Ok(entry) if entry.file_type().is_dir() => {}
+            Ok(entry) if entry.file_type().is_symlink() => totals.files += 1,
+            Ok(entry) => {
+                totals.files += 1;
+                totals.bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+            // ループや切れたリンクはリンクのままコピーされる
+            Err(e) if e.loop_ancestor().is_some() || e.path().is_some_and(is_symlink) => {
+                totals.files += 1
+            }
+            Err(_) => {}
+        }
+    }
+    totals
+}
+
+fn is_symlink(path: &Path) -> bool {
+    path.symlink_metadata().is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// リンクをたどらずにフォルダかどうかを判定する
+pub fn is_real_dir(path: &Path) -> bool {
+    path.symlink_metadata().is_ok_and(|m| m.is_dir())
+}
+
+/// 進捗を通知しながらファイルをコピーし、`options.preserve` に従って属性を引き継ぐ。
+/// reflink を最初に試し、できなければ穴を保ったまま（穴がなければ全体を）コピーする。
+/// `options.verify` なら書き込み後に内容を読み直して比較する
+pub fn copy_file(
+    src: &Path,
+    dst: &Path,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<(), OpError> {
+    let copy_err = |e: std::io::Error| {
+        OpError::io(
+            &e,
+            format!("コピー失敗: {} -> {}: {}", src.display(), dst.display(), e),
+        )
+    };
+
+    let mut reader = File::open(src).map_err(copy_err)?;
+    let metadata = reader.metadata().map_err(copy_err)?;
+    // 上書き先がリンクなら、リンク先ではなくリンク自体を置き換える
+    if is_symlink(dst) {
+        std::fs::remove_file(dst).map_err(copy_err)?;
+    }
+
+    let len = metadata.len();
+    let resume_at = tracker
+        .journal
+        .as_ref()
+        .and_then(|j| j.resume_offset(src, &metadata, dst));
+    tracker.begin_file(src, &metadata);
+    tracker.checkpoint()?;
+
+    let method = if let Some(offset) = resume_at {
+        // 中断したジョブの再開: 前回書き込んだ分の続きからコピーする
+        if offset < len {
+            let mut writer = OpenOptions::new().write(true).open(dst).map_err(copy_err)?;
+            reader.seek(SeekFrom::Start(offset)).map_err(copy_err)?;
+            writer.seek(SeekFrom::Start(offset)).map_err(copy_err)?;
+            tracker.advance(offset);
+            let mut buf = vec![0u8; BUFFER_SIZE];
+            copy_stream(&mut reader, &mut writer, &mut buf, tracker, &copy_err)?;
+        } else {
+            tracker.advance(len);
+        }
+        CopyMethod::Standard
+    } else if fastcopy::reflink(src, dst).is_ok() {
+        tracker.advance(len);
+        CopyMethod::Reflink
+    } else {
+        let mut writer = File::create(dst).map_err(copy_err)?;
+        tracker.destination_truncated(src);
+        let mut buf = vec![0u8; BUFFER_SIZE];
+        let segments = fastcopy::data_segments(&reader, len).map_err(copy_err)?;
+        let result = match &segments {
+            Some(segments) => {
+                copy_segments(&mut reader, &mut writer, segments, len, &mut buf, tracker, &copy_err)
+            }
+            None => copy_stream(&mut reader, &mut writer, &mut buf, tracker, &copy_err),
+        };
+        if let Err(e) = result {
+            // キャンセル時は書きかけのファイルを残さない
+            drop(writer);
+            if e.kind == ErrorKind::Cancelled {
+                let _ = std::fs::remove_file(dst);
+            }
+            return Err(e);
+        }
+        if segments.is_some() {
+            CopyMethod::Sparse
+        } else {
+            CopyMethod::Standard
+        }
+    };
+
+    if options.verify {
+        if let Err(e) = verify_copy(src, dst, tracker) {
+            // 検証できなかったコピーを残すと、後のコピーや統合で「コピー済み」と扱われてしまう
+            let _ = std::fs::remove_file(dst);
+            return Err(e);
+        }
+    }
+    preserve_metadata(src, &metadata, dst, options.preserve)?;
+    tracker.copy_methods.insert(method);
+    tracker.finish_file(src);
+    Ok(())
+}
+
+/// コピー元とコピー先の BLAKE3 ハッシュを比較する
+fn verify_copy(src: &Path, dst: &Path, tracker: &ProgressTracker) -> Result<(), OpError> {
+    if hash_file(src, tracker)? != hash_file(dst, tracker)? {
+        return Err(OpError::new(
+            ErrorKind::ChecksumMismatch,
+            format!("検証失敗（内容が一致しません）: {} -> {}", src.display(), dst.display()),
+        ));
+    }
+    Ok(())
+}
+
+fn hash_file(path: &Path, tracker: &ProgressTracker) -> Result<blake3::Hash, OpError> {
+    let read_err = |e: std::io::Error| OpError::io(&e, format!("検証失敗: {}: {}", path.display(), e));
+    let mut file = File::open(path).map_err(read_err)?;
+    let mut hasher = blake3::Hasher::new();
+    let mut buf = vec![0u8; BUFFER_SIZE];
+    loop {
+        tracker.checkpoint()?;
+        let n = file.read(&mut buf).map_err(read_err)?;
+        if n == 0 {
+            return Ok(hasher.finalize());
+        }
+        hasher.update(&buf[..n]);
+    }
+}
+
+/// 終端まで読み書きする
+fn copy_stream(
+    reader: &mut impl Read,
+    writer: &mut File,
+    buf: &mut [u8],
+    tracker: &mut ProgressTracker,
+    copy_err: &impl Fn(std::io::Error) -> OpError,
+) -> Result<(), OpError> {
+    loop {
+        tracker.checkpoint()?;
+        let n = reader.read(buf).map_err(copy_err)?;
+        if n == 0 {
+            return Ok(());
+        }
+        writer.write_all(&buf[..n]).map_err(copy_err)?;
+        tracker.advance(n as u64);
+    }
+}
+
+/// データ部分だけを同じ位置に書き込み、穴はサイズだけ合わせて残す
+fn copy_segments(
+    reader: &mut File,
+    writer: &mut File,
+    segments: &[(u64, u64)],
+    len: u64,
+    buf: &mut [u8],
+    tracker: &mut ProgressTracker,
+    copy_err: &impl Fn(std::io::Error) -> OpError,
+) -> Result<(), OpError> {
+    let mut pos = 0;
+    for &(start, end) in segments {
+        tracker.advance(start - pos);
+        reader.seek(SeekFrom::Start(start)).map_err(copy_err)?;
+        writer.seek(SeekFrom::Start(start)).map_err(copy_err)?;
+        let mut data = (&*reader).take(end - start);
+        copy_stream(&mut data, writer, buf, tracker, copy_err)?;
+        pos = end;
+    }
+    tracker.advance(len.saturating_sub(pos));
+    writer.set_len(len).map_err(copy_err)
+}
+
+/// `ancestors` はコピー中のフォルダの実パス。リンクをたどる場合のループ検出に使う
+fn copy_dir_recursive(
+    src: &Path,
+    dst: &Path,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<(), OpError> {
+    std::fs::create_dir_all(dst)
+        .map_err(|e| OpError::io(&e, format!("ディレクトリ作成失敗: {}", e)))?;
+
+    ancestors.push(src.canonicalize().unwrap_or_else(|_| src.to_path_buf()));
+    let read_err = |e: std::io::Error| OpError::io(&e, format!("{}: {}", src.display(), e));
+    for entry in std::fs::read_dir(src).map_err(read_err)? {
+        let entry = entry.map_err(read_err)?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        copy_entry(&src_path, &dst_path, options, tracker, ancestors)?;
+    }
+    ancestors.pop();
+
+    // 中身を書き込むと更新日時が変わるので、フォルダの属性は最後に設定する
+    preserve_dir_metadata(src, dst, options.preserve)
+}
+
+/// フォルダの属性を引き継ぐ
+fn preserve_dir_metadata(src: &Path, dst: &Path, preserve: PreserveOptions) -> Result<(), OpError> {
+    let metadata = std::fs::metadata(src)
+        .map_err(|e| OpError::io(&e, format!("{}: {}", src.display(), e)))?;
+    preserve_metadata(src, &metadata, dst, preserve)
+}
+
+/// コピー元の属性をコピー先へ設定する。
+/// パーミッションを先に落とすと書き込めなくなるため、拡張属性 → 所有者 → パーミッション → 日時の順に設定する。
+fn preserve_metadata(
+    src: &Path,
+    metadata: &Metadata,
+    dst: &Path,
+    preserve: PreserveOptions,
+) -> Result<(), OpError> {
+    let attr_err = |e: std::io::Error| {
+        OpError::io(&e, format!("属性の設定失敗: {}: {}", dst.display(), e))
+    };
+
+    #[cfg(unix)]
+    {
+        if preserve.xattrs {
+            copy_xattrs(src, dst);
+        }
+        if preserve.ownership {
+            use std::os::unix::fs::MetadataExt;
+            // root 以外は他人の所有にできないので、失敗しても続ける
+            let _ = std::os::unix::fs::chown(dst, Some(metadata.uid()), Some(metadata.gid()));
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = src;
+
+    if preserve.permissions {
+        std::fs::set_permissions(dst, metadata.permissions()).map_err(attr_err)?;
+    }
+    if preserve.timestamps {
+        let mtime = filetime::FileTime::from_last_modification_time(metadata);
+        let atime = filetime::FileTime::from_last_access_time(metadata);
+        filetime::set_file_times(dst, atime, mtime).map_err(attr_err)?;
+    }
+    Ok(())
+}
+
+/// 拡張属性をコピーする。コピー先のファイルシステムが対応していない属性や、
+/// 権限が必要な名前空間（security.* など）は引き継がない
+#[cfg(unix)]
+fn copy_xattrs(src: &Path, dst: &Path) {
+    let Ok(names) = xattr::list(src) else {
+        return;
+    };
+    for name in names {
+        if let Ok(Some(value)) = xattr::get(src, &name) {
+            let _ = xattr::set(dst, &name, &value);
+        }
+    }
+}
+
+/// フォルダを統合しながらコピーする。コピー先に既にあるファイルは
+/// `strategy` で 1 つずつ解決し、その結果を `conflicts` に記録する。
+pub fn merge_dir(
+    src: &Path,
+    dst: &Path,
+    strategy: ConflictStrategy,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+    conflicts: &mut Vec<NestedConflict>,
+) -> Result<(), OpError> {
+    std::fs::create_dir_all(dst)
+        .map_err(|e| OpError::io(&e, format!("ディレクトリ作成失敗: {}", e)))?;
+
+    let read_err = |e: std::io::Error| OpError::io(&e, format!("{}: {}", src.display(), e));
+    for entry in std::fs::read_dir(src).map_err(read_err)? {
+        let entry = entry.map_err(read_err)?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        if is_real_dir(&src_path) && dst_path.is_dir() {
+            merge_dir(&src_path, &dst_path, strategy, options, tracker, conflicts)?;
+            continue;
+        }
+        // 再開時、前回自分で書き込んだファイルは衝突として扱わない
+        let resumed = tracker.journal.as_ref().is_some_and(|j| j.touched(&src_path));
+        if !dst_path.exists() || resumed {
+            copy_path(&src_path, &dst_path, options, tracker)?;
+            continue;
+        }
+
+        let resolution = strategy.resolve(&src_path, &dst_path);
+        let mut renamed_to = None;
+        match resolution {
+            Resolution::Skip => {
+                tracker.complete_without_copy(&src_path, scan_path(&src_path, options.symlinks));
+            }
+            Resolution::Overwrite => copy_path(&src_path, &dst_path, options, tracker)?,
+            Resolution::Rename => {
+                let unique = generate_unique_name(&dst_path);
+                copy_path(&src_path, &unique, options, tracker)?;
+                renamed_to = Some(unique.to_string_lossy().to_string());
+            }
+        }
+        conflicts.push(NestedConflict {
+            path: dst_path.to_string_lossy().to_string(),
+            resolution,
+            renamed_to,
+        });
+    }
+    Ok(())
+}
+
+/// rename で移動し、別デバイスなどで失敗したらコピーしてから移動元を削除する
+pub fn move_path(
+    src: &Path,
+    dst: &Path,
+    totals: ScanTotals,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<(), OpError> {
+    // rename fails across drives, fall back to copy + delete
+    if std::fs::rename(src, dst).is_ok() {
+        tracker.complete_without_copy(src, totals);
+        return Ok(());
+    }
+
+    // 既存フォルダへの上書きは、中身を 1 つずつ移動する
+    if is_real_dir(src) && is_real_dir(dst) {
+        let strategy = ConflictStrategy::Overwrite;
+        return merge_move_dir(src, dst, strategy, options, tracker, &mut Vec::new())
+            .map_err(|e| e.with_state(ItemState::PartlyMoved));
+    }
+    move_by_copy(src, dst, options, tracker)
+}
+
+/// 移動先と同じフォルダの一時名へコピーし、完成してから rename で配置して移動元を消す。
+/// 失敗した場合は一時コピーを削除し、残った状態をエラーに記録する
+fn move_by_copy(
+    src: &Path,
+    dst: &Path,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<(), OpError> {
+    let file_name = dst.file_name().unwrap_or_default().to_string_lossy();
+    let mut temp = dst.with_file_name(format!(".{}.moving", file_name));
+    if temp.symlink_metadata().is_ok() {
+        temp = generate_unique_name(&temp);
+    }
+    let rollback = |temp: &Path| match remove_path(temp) {
+        Ok(()) => ItemState::SourceIntact,
+        Err(_) => ItemState::PartialDestination,
+    };
+
+    if let Err(e) = copy_path(src, &temp, options, tracker) {
+        return Err(e.with_state(rollback(&temp)));
+    }
+    if let Err(e) = std::fs::rename(&temp, dst) {
+        let state = rollback(&temp);
+        let message = format!("移動先への配置失敗: {}: {}", dst.display(), e);
+        return Err(OpError::io(&e, message).with_state(state));
+    }
+    remove_path(src).map_err(|e| {
+        OpError::io(&e, format!("移動元の削除失敗: {}: {}", src.display(), e))
+            .with_state(ItemState::BothPresent)
+    })
+}
+
+/// ファイル・フォルダ・リンクを削除する（存在しなければ何もしない）
+pub fn remove_path(path: &Path) -> std::io::Result<()> {
+    let removed = if is_real_dir(path) {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    };
+    match removed {
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        other => other,
+    }
+}
+
+/// `merge_dir` の移動版。スキップした項目は移動元に残り、空になったフォルダだけ削除する
+pub fn merge_move_dir(
+    src: &Path,
+    dst: &Path,
+    strategy: ConflictStrategy,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+    conflicts: &mut Vec<NestedConflict>,
+) -> Result<(), OpError> {
+    std::fs::create_dir_all(dst)
+        .map_err(|e| OpError::io(&e, format!("ディレクトリ作成失敗: {}", e)))?;
+
+    let read_err = |e: std::io::Error| OpError::io(&e, format!("{}: {}", src.display(), e));
+    for entry in std::fs::read_dir(src).map_err(read_err)? {
+        let entry = entry.map_err(read_err)?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        if is_real_dir(&src_path) && dst_path.is_dir() {
+            merge_move_dir(&src_path, &dst_path, strategy, options, tracker, conflicts)?;
+            continue;
+        }
+        let totals = scan_path(&src_path, options.symlinks);
+        if !dst_path.exists() {
+            move_path(&src_path, &dst_path, totals, options, tracker)?;
+            continue;
+        }
+
+        let resolution = strategy.resolve(&src_path, &dst_path);
+        let mut renamed_to = None;
+        match resolution {
+            Resolution::Skip => tracker.complete_without_copy(&src_path, totals),
+            Resolution::Overwrite => move_path(&src_path, &dst_path, totals, options, tracker)?,
+            Resolution::Rename => {
+                let unique = generate_unique_name(&dst_path);
+                move_path(&src_path, &unique, totals, options, tracker)?;
+                renamed_to = Some(unique.to_string_lossy().to_string());
+            }
+        }
+        conflicts.push(NestedConflict {
+            path: dst_path.to_string_lossy().to_string(),
+            resolution,
+            renamed_to,
+        });
+    }
+
+    // スキップした項目が残っていれば削除に失敗するが、それで正しい
+    let _ = std::fs::remove_dir(src);
+    Ok(())
+}
+
+/// `target` が `dir` 自身かその配下にあるか（フォルダを自分の中へコピー・移動するのを防ぐ）
+pub fn is_same_or_descendant(dir: &Path, target: &Path) -> bool {
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let target = target.canonicalize().unwrap_or_else(|_| target.to_path_buf());
+    target.starts_with(&dir)
+}
+
+/// `name (2).ext` のように番号を付けて、存在しないパスを作る
+pub fn generate_unique_name(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new("."));
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let ext = path.extension().map(|e| format!(".{}", e.to_string_lossy())).unwrap_or_default();
+
+    let mut n = 2;
+    loop {
+        let candidate = parent.join(format!("{} ({}){}", stem, n, ext));
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// ファイル・ディレクトリ・リンクのどれでもコピーする
+pub fn copy_path(
+    src: &Path,
+    dst: &Path,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+) -> Result<(), OpError> {
+    copy_entry(src, dst, options, tracker, &mut Vec::new())
+}
+
+fn copy_entry(
+    src: &Path,
+    dst: &Path,
+    options: &TransferOptions,
+    tracker: &mut ProgressTracker,
+    ancestors: &mut Vec<PathBuf>,
+) -> Result<(), OpError> {
+    if is_symlink(src) {
+        match options.symlinks {
+            SymlinkPolicy::CopyAsLink => return copy_symlink(src, dst, tracker),
+            SymlinkPolicy::Skip => {
+                tracker.complete_without_copy(src, LINK_TOTALS);
+                return Ok(());
+            }
+            SymlinkPolicy::Follow => {
+                let Ok(target) = std::fs::metadata(src) else {
+                    // 切れたリンク
+                    return copy_symlink(src, dst, tracker);
+                };
+                let looped = target.is_dir()
+                    && src.canonicalize().is_ok_and(|real| ancestors.contains(&real));
+                if looped {
+                    return copy_symlink(src, dst, tracker);
+                }
+            }
+        }
+    }
+
+    if src.is_dir() {
+        copy_dir_recursive(src, dst, options, tracker, ancestors)
+    } else {
+        copy_file(src, dst, options, tracker)
+    }
+}
+
+/// リンク先をたどらず、リンクそのものを作り直す
+fn copy_symlink(src: &Path, dst: &Path, tracker: &mut ProgressTracker) -> Result<(), OpError> {
+    let link_err = |e: std::io::Error| {
+        OpError::io(
+            &e,
+            format!("リンクのコピー失敗: {} -> {}: {}", src.display(), dst.display(), e),
+        )
+    };
+    tracker.checkpoint()?;
+    let target = std::fs::read_link(src).map_err(link_err)?;
+    if dst.symlink_metadata().is_ok_and(|m| !m.is_dir()) {
+        std::fs::remove_file(dst).map_err(link_err)?;
+    }
+
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&target, dst).map_err(link_err)?;
+    #[cfg(windows)]
+    {
+        if src.is_dir() {
+            std::os::windows::fs::symlink_dir(&target, dst).map_err(link_err)?;
+        } else {
+            std::os::windows::fs::symlink_file(&target, dst).map_err(link_err)?;
+        }
+    }
+
+    tracker.complete_without_copy(src, LINK_TOTALS);
+    Ok(())
+}
+
+/// 実行前に決定した 1 項目分の処理内容
+pub enum PlannedItem {
+    Transfer(PlannedTransfer),
+    Skipped { source: String },
+    Invalid { source: String, error: OpError },
+}
+
+/// コピー・移動の計画（実行前に衝突を解決し、事前スキャンする）
+pub struct PlannedTransfer {
+    pub src: PathBuf,
+    pub dest: PathBuf,
+    pub totals: ScanTotals,
+    /// 既存フォルダへ統合する場合の、フォルダ内の衝突解決方法
+    pub merge: Option<ConflictStrategy>,
+    /// 計画した時点でコピー先に何もなかった（上書き・統合ではない）
+    pub creates: bool,
+}
+
+impl PlannedTransfer {
+    pub fn new(src: &Path, dest: PathBuf, symlinks: SymlinkPolicy) -> Self {
+        Self {
+            src: src.to_path_buf(),
+            totals: scan_path(src, symlinks),
+            merge: None,
+            creates: dest.symlink_metadata().is_err(),
+            dest,
+        }
+    }
+
+    pub fn merging(mut self, strategy: ConflictStrategy) -> Self {
+        self.merge = Some(strategy);
+        self
+    }
+}
+
+/// 計画した項目を順に実行し、項目ごとの結果を集める。
+/// `continue_on_error` が偽なら最初の失敗で、それまでの結果と一緒にそのエラーを返す。
+/// キャンセルは常にバッチ全体を中断する。
+pub fn run_batch<F>(
+    plan: Vec<PlannedItem>,
+    continue_on_error: bool,
+    tracker: &mut ProgressTracker,
+    mut op: F,
+) -> Result<BatchReport, BatchFailure>
+where
+    F: FnMut(&PlannedTransfer, &mut ProgressTracker) -> Result<ItemDetails, OpError>,
+{
+    // 計画段階で失敗が分かっていれば、何も変更しないうちに中断する
+    if !continue_on_error {
+        if let Some(PlannedItem::Invalid { error, .. }) =
+            plan.iter().find(|i| matches!(i, PlannedItem::Invalid { .. }))
+        {
+            return Err(error.clone().into());
+        }
+    }
+    for item in &plan {
+        if let PlannedItem::Transfer(transfer) = item {
+            tracker.add_total(transfer.totals);
+        }
+    }
+    if let Some(journal) = tracker.journal.as_mut() {
+        journal.record_plan(&plan);
+    }
+
+    let mut report = BatchReport::default();
+    for item in plan {
+        let result = match item {
+            PlannedItem::Transfer(transfer) => {
+                let outcome = tracker
+                    .checkpoint()
+                    .and_then(|_| op(&transfer, tracker));
+                let copy_methods = tracker.take_copy_methods();
+                let (status, error, details) = match outcome {
+                    Ok(details) => (
+                        ItemStatus::Succeeded,
+                        None,
+                        ItemDetails {
+                            copy_methods,
+                            ..details
+                        },
+                    ),
+                    Err(error) if error.kind == ErrorKind::Cancelled || !continue_on_error => {
+                        return Err(BatchFailure { error, report });
+                    }
+                    Err(e) => (ItemStatus::Failed, Some(e), ItemDetails::default()),
+                };
+                ItemResult {
+                    source: transfer.src.to_string_lossy().to_string(),
+                    destination: Some(transfer.dest.to_string_lossy().to_string()),
+                    status,
+                    error,
+                    details,
+                }
+            }
+            PlannedItem::Skipped { source } => ItemResult {
+                source,
+                destination: None,
+                status: ItemStatus::Skipped,
+                error: None,
+                details: ItemDetails::default(),
+            },
+            PlannedItem::Invalid { source, error } => {
+                if !continue_on_error {
+                    return Err(BatchFailure { error, report });
+                }
+                ItemResult {
+                    source,
+                    destination: None,
+                    status: ItemStatus::Failed,
+                    error: Some(error),
+                    details: ItemDetails::default(),
+                }
+            }
+        };
+        report.items.push(result);
+    }
+    tracker.finish();
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn recording_tracker() -> (ProgressTracker, Arc<Mutex<Vec<TransferProgress>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let tracker = ProgressTracker::new(
+            Some("op-1".to_string()),
+            Box::new(move |p| sink.lock().unwrap().push(p.clone())),
+        );
+        (tracker, events)
+    }
+
+    #[test]
+    fn scan_path_counts_nested_files() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.txt"), "12345").unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("sub").join("b.txt"), "123").unwrap();
+
+        let totals = scan_path(dir.path(), SymlinkPolicy::CopyAsLink);
+        assert_eq!(totals, ScanTotals { bytes: 8, files: 2 });
+    }
+
+    #[test]
+    fn scan_path_single_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.txt");
+        fs::write(&file, "hello").unwrap();
+
+        assert_eq!(scan_path(&file, SymlinkPolicy::CopyAsLink), ScanTotals { bytes: 5, files: 1 });
+    }
+
+    #[test]
+    fn copy_path_reports_batch_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("nested")).unwrap();
+        fs::write(src.join("a.txt"), "aaaa").unwrap();
+        fs::write(src.join("nested").join("b.txt"), "bb").unwrap();
+
+        let (mut tracker, events) = recording_tracker();
+        tracker.add_total(scan_path(&src, SymlinkPolicy::CopyAsLink));
+        copy_path(&src, &dir.path().join("dst"), &TransferOptions::default(), &mut tracker).unwrap();
+        tracker.finish();
+
+        let events = events.lock().unwrap();
+        let last = events.last().unwrap();
+        assert_eq!(last.operation_id.as_deref(), Some("op-1"));
+        assert_eq!(last.bytes_copied, 6);
+        assert_eq!(last.bytes_total, 6);
+        assert_eq!(last.items_done, 2);
+        assert_eq!(last.items_total, 2);
+    }
+
+    #[test]
+    fn copy_file_reports_file_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.bin");
+        fs::write(&src, vec![7u8; 3000]).unwrap();
+
+        let (mut tracker, events) = recording_tracker();
+        copy_file(&src, &dir.path().join("dst.bin"), &TransferOptions::default(), &mut tracker)
+            .unwrap();
+        tracker.finish();
+
+        let events = events.lock().unwrap();
+        let last = events.last().unwrap();
+        assert_eq!(last.file_bytes_copied, 3000);
+        assert_eq!(last.file_bytes_total, 3000);
+        assert!(last.current_file.ends_with("src.bin"));
+        assert_eq!(fs::read(dir.path().join("dst.bin")).unwrap().len(), 3000);
+    }
+
+    #[test]
+    fn copy_file_cancelled_removes_partial_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src.bin");
+        fs::write(&src, "data").unwrap();
+        let dst = dir.path().join("dst.bin");
+
+        let control = Arc::new(JobControl::new());
+        control.cancel();
+        let mut tracker = ProgressTracker::silent().with_control(control);
+
+        let result = copy_file(&src, &dst, &TransferOptions::default(), &mut tracker);
+        assert_eq!(result.unwrap_err().kind, ErrorKind::Cancelled);
+        assert!(!dst.exists());
+    }
+
+    #[test]
+    fn copy_path_preserves_dir_timestamps() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        let old = filetime::FileTime::from_unix_time(1_000_000_000, 0);
+        filetime::set_file_times(src.join("a.txt"), old, old).unwrap();
+        filetime::set_file_times(&src, old, old).unwrap();
+
+        let options = TransferOptions {
+            preserve: PreserveOptions {
+                timestamps: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let dst = dir.path().join("dst");
+        copy_path(&src, &dst, &options, &mut ProgressTracker::silent()).unwrap();
+
+        let mtime = |p: &Path| {
+            filetime::FileTime::from_last_modification_time(&fs::metadata(p).unwrap())
+        };
+        assert_eq!(mtime(&dst.join("a.txt")), old);
+        assert_eq!(mtime(&dst), old);
+    }
+
+    #[test]
+    fn copy_file_without_preserve_gets_fresh_mtime() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "a").unwrap();
+        let old = filetime::FileTime::from_unix_time(1_000_000_000, 0);
+        filetime::set_file_times(&src, old, old).unwrap();
+
+        let dst = dir.path().join("b.txt");
+        copy_file(&src, &dst, &TransferOptions::default(), &mut ProgressTracker::silent())
+            .unwrap();
+        let mtime = filetime::FileTime::from_last_modification_time(&fs::metadata(&dst).unwrap());
+        assert_ne!(mtime, old);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_file_preserves_mode_by_default() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("run.sh");
+        fs::write(&src, "#!/bin/sh").unwrap();
+        fs::set_permissions(&src, fs::Permissions::from_mode(0o750)).unwrap();
+
+        let dst = dir.path().join("copy.sh");
+        copy_file(&src, &dst, &TransferOptions::default(), &mut ProgressTracker::silent())
+            .unwrap();
+        assert_eq!(fs::metadata(&dst).unwrap().permissions().mode() & 0o777, 0o750);
+    }
+
+    #[cfg(unix)]
+    fn link_options(symlinks: SymlinkPolicy) -> TransferOptions {
+        TransferOptions {
+            symlinks,
+            ..Default::default()
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_path_copies_links_as_links_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("big")).unwrap();
+        fs::write(src.join("big").join("data.bin"), "data").unwrap();
+        std::os::unix::fs::symlink("big", src.join("link")).unwrap();
+        std::os::unix::fs::symlink("missing", src.join("dangling")).unwrap();
+
+        let dst = dir.path().join("dst");
+        let options = TransferOptions::default();
+        assert_eq!(
+            scan_path(&src, options.symlinks),
+            ScanTotals { bytes: 4, files: 3 }
+        );
+        copy_path(&src, &dst, &options, &mut ProgressTracker::silent()).unwrap();
+
+        assert_eq!(fs::read_link(dst.join("link")).unwrap(), Path::new("big"));
+        assert_eq!(fs::read_link(dst.join("dangling")).unwrap(), Path::new("missing"));
+        assert!(dst.join("big").join("data.bin").is_file());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_path_follow_links_detects_loops() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("sub").join("a.txt"), "a").unwrap();
+        std::os::unix::fs::symlink("..", src.join("sub").join("up")).unwrap();
+        std::os::unix::fs::symlink("sub", src.join("alias")).unwrap();
+        std::os::unix::fs::symlink("missing", src.join("dangling")).unwrap();
+
+        let dst = dir.path().join("dst");
+        let options = link_options(SymlinkPolicy::Follow);
+        copy_path(&src, &dst, &options, &mut ProgressTracker::silent()).unwrap();
+
+        // alias は実体としてコピーされ、ループするリンクと切れたリンクはリンクのまま
+        assert!(!is_symlink(&dst.join("alias")));
+        assert!(dst.join("alias").join("a.txt").is_file());
+        assert!(is_symlink(&dst.join("sub").join("up")));
+        assert!(is_symlink(&dst.join("dangling")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_path_skip_links() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.txt"), "a").unwrap();
+        std::os::unix::fs::symlink("a.txt", src.join("link")).unwrap();
+
+        let dst = dir.path().join("dst");
+        let options = link_options(SymlinkPolicy::Skip);
+        let (mut tracker, events) = recording_tracker();
+        tracker.add_total(scan_path(&src, options.symlinks));
+        copy_path(&src, &dst, &options, &mut tracker).unwrap();
+        tracker.finish();
+
+        assert!(dst.join("a.txt").is_file());
+        assert!(dst.join("link").symlink_metadata().is_err());
+        let last = events.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(last.items_done, last.items_total);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn copy_file_over_link_replaces_link() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("target.txt");
+        fs::write(&target, "keep").unwrap();
+        let dst = dir.path().join("dst.txt");
+        std::os::unix::fs::symlink(&target, &dst).unwrap();
+        let src = dir.path().join("src.txt");
+        fs::write(&src, "new").unwrap();
+
+        copy_path(&src, &dst, &TransferOptions::default(), &mut ProgressTracker::silent())
+            .unwrap();
+        assert_eq!(fs::read_to_string(&target).unwrap(), "keep");
+        assert!(!is_symlink(&dst));
+        assert_eq!(fs::read_to_string(&dst).unwrap(), "new");
+    }
+
+    #[test]
+    fn run_batch_reports_copy_method() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+        let plan = vec![PlannedItem::Transfer(PlannedTransfer::new(
+            &src,
+            dir.path().join("b.txt"),
+            SymlinkPolicy::CopyAsLink,
+        ))];
+        let report = run_batch(plan, false, &mut ProgressTracker::silent(), |t, tracker| {
+            copy_path(&t.src, &t.dest, &TransferOptions::default(), tracker)
+                .map(|_| ItemDetails::default())
+        })
+        .unwrap();
+
+        // reflink できるかはファイルシステム次第
+        let methods = &report.items[0].details.copy_methods;
+        assert_eq!(methods.len(), 1);
+        assert_eq!(fs::read_to_string(dir.path().join("b.txt")).unwrap(), "data");
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn copy_file_keeps_holes() {
+        use std::os::unix::fs::MetadataExt;
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("disk.img");
+        let mut file = File::create(&src).unwrap();
+        file.write_all(b"head").unwrap();
+        file.seek(SeekFrom::Start(16 * 1024 * 1024)).unwrap();
+        file.write_all(b"tail").unwrap();
+        drop(file);
+
+        let dst = dir.path().join("copy.img");
+        let (mut tracker, events) = recording_tracker();
+        tracker.add_total(scan_path(&src, SymlinkPolicy::CopyAsLink));
+        copy_file(&src, &dst, &TransferOptions::default(), &mut tracker).unwrap();
+        tracker.finish();
+
+        let copied = fs::read(&dst).unwrap();
+        assert_eq!(copied.len(), 16 * 1024 * 1024 + 4);
+        assert_eq!(&copied[..4], b"head");
+        assert_eq!(&copied[copied.len() - 4..], b"tail");
+        assert!(copied[4..copied.len() - 4].iter().all(|&b| b == 0));
+        // 元ファイルに穴がある（ファイルシステムが対応している）なら、コピーにも穴が残る
+        if fs::metadata(&src).unwrap().blocks() * 512 < 16 * 1024 * 1024 {
+            assert!(fs::metadata(&dst).unwrap().blocks() * 512 < 16 * 1024 * 1024);
+        }
+        let last = events.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(last.bytes_copied, last.bytes_total);
+    }
+
+    #[test]
+    fn verify_copy_detects_mismatch() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.bin");
+        let dst = dir.path().join("b.bin");
+        fs::write(&src, "same").unwrap();
+        fs::write(&dst, "same").unwrap();
+        let tracker = ProgressTracker::silent();
+        assert!(verify_copy(&src, &dst, &tracker).is_ok());
+
+        fs::write(&dst, "diff").unwrap();
+        let err = verify_copy(&src, &dst, &tracker).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ChecksumMismatch);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn failed_verification_removes_destination() {
+        // 読むたびに内容が変わるので、コピー後の検証は必ず失敗する
+        let src = Path::new("/proc/sys/kernel/random/uuid");
+        if !src.exists() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let dst = dir.path().join("uuid");
+        let options = TransferOptions {
+            verify: true,
+            ..Default::default()
+        };
+        let err = copy_file(src, &dst, &options, &mut ProgressTracker::silent()).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::ChecksumMismatch);
+        assert!(!dst.exists());
+    }
+
+    #[test]
+    fn copy_path_with_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.bin"), vec![3u8; 3 * BUFFER_SIZE + 7]).unwrap();
+
+        let options = TransferOptions {
+            verify: true,
+            ..Default::default()
+        };
+        let dst = dir.path().join("dst");
+        copy_path(&src, &dst, &options, &mut ProgressTracker::silent()).unwrap();
+        assert_eq!(fs::read(dst.join("a.bin")).unwrap(), fs::read(src.join("a.bin")).unwrap());
+    }
+
+    #[test]
+    fn move_by_copy_replaces_destination_and_removes_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::write(src.join("sub").join("a.txt"), "a").unwrap();
+        let dst = dir.path().join("dest").join("src");
+        fs::create_dir_all(dst.parent().unwrap()).unwrap();
+
+        move_by_copy(&src, &dst, &TransferOptions::default(), &mut ProgressTracker::silent())
+            .unwrap();
+
+        assert!(!src.exists());
+        assert_eq!(fs::read_to_string(dst.join("sub").join("a.txt")).unwrap(), "a");
+        let leftovers: Vec<_> = fs::read_dir(dst.parent().unwrap()).unwrap().collect();
+        assert_eq!(leftovers.len(), 1);
+    }
+
+    #[test]
+    fn move_by_copy_failure_cleans_up_temp() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("a.txt");
+        fs::write(&src, "data").unwrap();
+        let dest_dir = dir.path().join("dest");
+        fs::create_dir(&dest_dir).unwrap();
+        fs::write(dest_dir.join("a.txt"), "old").unwrap();
+
+        let control = Arc::new(JobControl::new());
+        control.cancel();
+        let mut tracker = ProgressTracker::silent().with_control(control);
+        let dst = dest_dir.join("a.txt");
+        let err = move_by_copy(&src, &dst, &TransferOptions::default(), &mut tracker).unwrap_err();
+
+        assert_eq!(err.state, Some(ItemState::SourceIntact));
+        assert_eq!(fs::read_to_string(&src).unwrap(), "data");
+        assert_eq!(fs::read_to_string(dest_dir.join("a.txt")).unwrap(), "old");
+        assert!(!dest_dir.join(".a.txt.moving").exists());
+    }
+
+    #[test]
+    fn job_control_resume_releases_checkpoint() {
+        let control = Arc::new(JobControl::new());
+        control.pause();
+
+        let waiter = {
+            let control = control.clone();
+            std::thread::spawn(move || control.checkpoint())
+        };
+        std::thread::sleep(Duration::from_millis(50));
+        assert!(!waiter.is_finished());
+
+        control.resume();
+        assert!(waiter.join().unwrap().is_ok());
+    }
+
+    #[test]
+    fn job_control_cancel_while_paused() {
+        let control = Arc::new(JobControl::new());
+        control.pause();
+
+        let waiter = {
+            let control = control.clone();
+            std::thread::spawn(move || control.checkpoint())
+        };
+        control.cancel();
+        assert!(waiter.join().unwrap().is_err());
+    }
+
+    #[test]
+    fn merge_dir_applies_strategy_to_nested_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dst = dir.path().join("dst");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::create_dir_all(dst.join("sub")).unwrap();
+        fs::write(src.join("new.txt"), "new").unwrap();
+        fs::write(src.join("sub").join("both.txt"), "from src").unwrap();
+        fs::write(dst.join("sub").join("both.txt"), "from dst").unwrap();
+        fs::write(dst.join("only_dst.txt"), "keep").unwrap();
+
+        let mut conflicts = Vec::new();
+        merge_dir(
+            &src,
+            &dst,
+            ConflictStrategy::Rename,
+            &TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+            &mut conflicts,
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(dst.join("new.txt")).unwrap(), "new");
+        assert_eq!(fs::read_to_string(dst.join("only_dst.txt")).unwrap(), "keep");
+        assert_eq!(
+            fs::read_to_string(dst.join("sub").join("both.txt")).unwrap(),
+            "from dst"
+        );
+        assert_eq!(
+            fs::read_to_string(dst.join("sub").join("both (2).txt")).unwrap(),
+            "from src"
+        );
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].resolution, Resolution::Rename);
+        assert!(conflicts[0].renamed_to.as_ref().unwrap().ends_with("both (2).txt"));
+    }
+
+    #[test]
+    fn merge_dir_skip_counts_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dst = dir.path().join("dst");
+        fs::create_dir_all(&src).unwrap();
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(src.join("a.txt"), "aaaa").unwrap();
+        fs::write(dst.join("a.txt"), "old").unwrap();
+
+        let (mut tracker, events) = recording_tracker();
+        tracker.add_total(scan_path(&src, SymlinkPolicy::CopyAsLink));
+        let mut conflicts = Vec::new();
+        merge_dir(
+            &src,
+            &dst,
+            ConflictStrategy::Skip,
+            &TransferOptions::default(),
+            &mut tracker,
+            &mut conflicts,
+        )
+        .unwrap();
+        tracker.finish();
+
+        assert_eq!(fs::read_to_string(dst.join("a.txt")).unwrap(), "old");
+        assert_eq!(conflicts[0].resolution, Resolution::Skip);
+        let last = events.lock().unwrap().last().cloned().unwrap();
+        assert_eq!(last.bytes_copied, last.bytes_total);
+    }
+
+    #[test]
+    fn merge_move_dir_leaves_skipped_files_in_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        let dst = dir.path().join("dst");
+        fs::create_dir_all(src.join("sub")).unwrap();
+        fs::create_dir_all(&dst).unwrap();
+        fs::write(src.join("keep.txt"), "src").unwrap();
+        fs::write(dst.join("keep.txt"), "dst").unwrap();
+        fs::write(src.join("sub").join("moved.txt"), "moved").unwrap();
+
+        let mut conflicts = Vec::new();
+        merge_move_dir(
+            &src,
+            &dst,
+            ConflictStrategy::Skip,
+            &TransferOptions::default(),
+            &mut ProgressTracker::silent(),
+            &mut conflicts,
+        )
+        .unwrap();
+
+        assert_eq!(fs::read_to_string(dst.join("sub").join("moved.txt")).unwrap(), "moved");
+        assert!(!src.join("sub").exists());
+        assert_eq!(fs::read_to_string(src.join("keep.txt")).unwrap(), "src");
+        assert_eq!(fs::read_to_string(dst.join("keep.txt")).unwrap(), "dst");
+        assert_eq!(conflicts.len(), 1);
+    }
+
+    #[test]
+    fn is_same_or_descendant_detects_nesting() {
+        let dir = tempfile::tempdir().unwrap();
+        let parent = dir.path().join("parent");
+        let child = parent.join("child");
+        fs::create_dir_all(&child).unwrap();
+        let sibling = dir.path().join("parent_sibling");
+        fs::create_dir_all(&sibling).unwrap();
+
+        assert!(is_same_or_descendant(&parent, &parent));
+        assert!(is_same_or_descendant(&parent, &child));
+        assert!(!is_same_or_descendant(&parent, dir.path()));
+        assert!(!is_same_or_descendant(&parent, &sibling));
+    }
+
+    #[test]
+    fn run_batch_continues_past_failures() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.txt");
+        fs::write(&good, "data").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let plan = vec![
+            PlannedItem::Transfer(PlannedTransfer::new(
+                &dir.path().join("missing.txt"),
+                dest.join("missing.txt"),
+                SymlinkPolicy::CopyAsLink,
+            )),
+            PlannedItem::Skipped {
+                source: "skipped.txt".to_string(),
+            },
+            PlannedItem::Transfer(PlannedTransfer::new(
+                &good,
+                dest.join("good.txt"),
+                SymlinkPolicy::CopyAsLink,
+            )),
+        ];
+        let report = run_batch(plan, true, &mut ProgressTracker::silent(), |t, tracker| {
+            copy_path(&t.src, &t.dest, &TransferOptions::default(), tracker)
+                .map(|_| ItemDetails::default())
+        })
+        .unwrap();
+
+        let statuses: Vec<ItemStatus> = report.items.iter().map(|i| i.status).collect();
+        assert_eq!(
+            statuses,
+            vec![ItemStatus::Failed, ItemStatus::Skipped, ItemStatus::Succeeded]
+        );
+        assert_eq!(report.items[0].error.as_ref().unwrap().kind, ErrorKind::NotFound);
+        assert!(dest.join("good.txt").exists());
+    }
+
+    #[test]
+    fn run_batch_stops_on_first_failure_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let good = dir.path().join("good.txt");
+        fs::write(&good, "data").unwrap();
+        let dest = dir.path().join("dest");
+        fs::create_dir(&dest).unwrap();
+
+        let plan = vec![
+            PlannedItem::Invalid {
+                source: "/".to_string(),
+                error: OpError::new(ErrorKind::InvalidInput, "bad"),
+            },
+            PlannedItem::Transfer(PlannedTransfer::new(
+                &good,
+                dest.join("good.txt"),
+                SymlinkPolicy::CopyAsLink,
+            )),
+        ];
+        let result = run_batch(plan, false, &mut ProgressTracker::silent(), |t, tracker| {
+            copy_path(&t.src, &t.dest, &TransferOptions::default(), tracker)
+                .map(|_| ItemDetails::default())
+        });
+
+        assert_eq!(result.unwrap_err().error.message, "bad");
+        assert!(!dest.join("good.txt").exists());
+    }
+
+    #[test]
+    fn run_batch_cancel_aborts_even_when_continuing() {
+        let control = Arc::new(JobControl::new());
+        control.cancel();
+        let mut tracker = ProgressTracker::silent().with_control(control);
+
+        let plan = vec![PlannedItem::Transfer(PlannedTransfer {
+            src: PathBuf::from("a"),
+            dest: PathBuf::from("b"),
+            totals: ScanTotals::default(),
+            merge: None,
+            creates: true,
+        })];
+        let result = run_batch(plan, true, &mut tracker, |_, _| Ok(ItemDetails::default()));
+        assert_eq!(result.unwrap_err().error.message, CANCELLED_MESSAGE);
+    }
+
+    #[test]
+    fn complete_without_copy_advances_totals() {
+        let (mut tracker, events) = recording_tracker();
+        let totals = ScanTotals { bytes: 10, files: 3 };
+        tracker.add_total(totals);
+        tracker.complete_without_copy(Path::new("/tmp/x"), totals);
+        tracker.finish();
+
+        let events = events.lock().unwrap();
+        let last = events.last().unwrap();
+        assert_eq!(last.bytes_copied, 10);
+        assert_eq!(last.items_done, 3);
+    }
+}
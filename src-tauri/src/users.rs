@@ -0,0 +1,100 @@
+//! ユーザー・グループの名前と ID の相互変換（Unix のみ）
+
+use std::ffi::{CStr, CString};
+
+const INITIAL_BUFFER: usize = 1024;
+const MAX_BUFFER: usize = 1 << 20;
+
+/// `getpwuid_r` などを、バッファが足りなければ広げながら呼ぶ
+fn lookup<T, R>(
+    mut call: impl FnMut(&mut T, &mut [libc::c_char], &mut *mut T) -> libc::c_int,
+    read: impl FnOnce(&T) -> R,
+) -> Option<R> {
+    let mut buf = vec![0 as libc::c_char; INITIAL_BUFFER];
+    loop {
+        let mut record: T = unsafe { std::mem::zeroed() };
+        let mut result = std::ptr::null_mut();
+        let ret = call(&mut record, &mut buf, &mut result);
+        if ret == libc::ERANGE && buf.len() < MAX_BUFFER {
+            buf.resize(buf.len() * 2, 0);
+            continue;
+        }
+        if ret != 0 || result.is_null() {
+            return None;
+        }
+        return Some(read(&record));
+    }
+}
+
+pub fn user_name(uid: u32) -> Option<String> {
+    lookup(
+        |pwd: &mut libc::passwd, buf, result| unsafe {
+            libc::getpwuid_r(uid, pwd, buf.as_mut_ptr(), buf.len(), result)
+        },
+        |pwd| {
+            unsafe { CStr::from_ptr(pwd.pw_name) }
+                .to_string_lossy()
+                .to_string()
+        },
+    )
+}
+
+pub fn group_name(gid: u32) -> Option<String> {
+    lookup(
+        |grp: &mut libc::group, buf, result| unsafe {
+            libc::getgrgid_r(gid, grp, buf.as_mut_ptr(), buf.len(), result)
+        },
+        |grp| {
+            unsafe { CStr::from_ptr(grp.gr_name) }
+                .to_string_lossy()
+                .to_string()
+        },
+    )
+}
+
+/// ユーザー名か数値の uid
+pub fn user_id(name: &str) -> Option<u32> {
+    if let Ok(uid) = name.parse() {
+        return Some(uid);
+    }
+    let name = CString::new(name).ok()?;
+    lookup(
+        |pwd: &mut libc::passwd, buf, result| unsafe {
+            libc::getpwnam_r(name.as_ptr(), pwd, buf.as_mut_ptr(), buf.len(), result)
+        },
+        |pwd| pwd.pw_uid,
+    )
+}
+
+/// グループ名か数値の gid
+pub fn group_id(name: &str) -> Option<u32> {
+    if let Ok(gid) = name.parse() {
+        return Some(gid);
+    }
+    let name = CString::new(name).ok()?;
+    lookup(
+        |grp: &mut libc::group, buf, result| unsafe {
+            libc::getgrnam_r(name.as_ptr(), grp, buf.as_mut_ptr(), buf.len(), result)
+        },
+        |grp| grp.gr_gid,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_and_ids_round_trip() {
+        let uid = unsafe { libc::getuid() };
+        let gid = unsafe { libc::getgid() };
+        if let Some(name) = user_name(uid) {
+            assert_eq!(user_id(&name), Some(uid));
+        }
+        if let Some(name) = group_name(gid) {
+            assert_eq!(group_id(&name), Some(gid));
+        }
+        assert_eq!(user_id("1234"), Some(1234));
+        assert_eq!(user_id("no-such-user-xyz"), None);
+    }
+}
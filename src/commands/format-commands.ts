@@ -0,0 +1,10 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { DateStyle, Language } from "../types";
+
+/** FileEntry.modified などの表示形式を切り替える。以降に読み込んだ一覧から反映される */
+export async function setDateFormat(
+  style: DateStyle,
+  language: Language
+): Promise<void> {
+  return invoke<void>("set_date_format", { settings: { style, language } });
+}
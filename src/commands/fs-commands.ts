@@ -1,9 +1,47 @@
-import { invoke } from "@tauri-apps/api/core";
+import { invoke, Channel } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import { open } from "@tauri-apps/plugin-shell";
-import type { FileEntry } from "../types";
+import type {
+  BatchReport,
+  CopyConflict,
+  DeleteMode,
+  DirectoryEvent,
+  FileEntry,
+  FileProperties,
+  ListOptions,
+  PermissionRules,
+  RenameRules,
+  TransferOptions,
+  TransferProgress,
+} from "../types";
 
-export async function readDirectory(path: string): Promise<FileEntry[]> {
-  return invoke<FileEntry[]>("read_directory", { path });
+export async function readDirectory(
+  path: string,
+  options?: ListOptions
+): Promise<FileEntry[]> {
+  return invoke<FileEntry[]>("read_directory", { path, options: options ?? null });
+}
+
+/**
+ * フォルダの中身をバッチに分けて受け取る。最初の 1 画面分はすぐ届く。
+ * 戻り値のストリーム ID を cancelDirectoryStream に渡すと途中で止められる
+ */
+export async function readDirectoryStream(
+  path: string,
+  onEvent: (event: DirectoryEvent) => void,
+  options?: ListOptions
+): Promise<number> {
+  const channel = new Channel<DirectoryEvent>();
+  channel.onmessage = onEvent;
+  return invoke<number>("read_directory_stream", {
+    path,
+    options: options ?? null,
+    onEvent: channel,
+  });
+}
+
+export async function cancelDirectoryStream(streamId: number): Promise<void> {
+  return invoke<void>("cancel_directory_stream", { streamId });
 }
 
 let homeDirCache: string | null = null;
@@ -16,22 +54,48 @@ export async function getHomeDir(): Promise<string> {
 
 export async function copyItems(
   sources: string[],
-  destination: string
-): Promise<void> {
-  return invoke("copy_items", { sources, destination });
+  destination: string,
+  options?: TransferOptions,
+  operationId?: string
+): Promise<BatchReport> {
+  return invoke<BatchReport>("copy_items", {
+    sources,
+    destination,
+    options: options ?? null,
+    operationId: operationId ?? null,
+  });
 }
 
 export async function moveItems(
   sources: string[],
-  destination: string
-): Promise<void> {
-  return invoke("move_items", { sources, destination });
+  destination: string,
+  strategy?: string,
+  options?: TransferOptions,
+  operationId?: string
+): Promise<BatchReport> {
+  return invoke<BatchReport>("move_items", {
+    sources,
+    destination,
+    strategy: strategy ?? null,
+    options: options ?? null,
+    operationId: operationId ?? null,
+  });
 }
 
-export async function deleteItems(paths: string[]): Promise<void> {
-  return invoke("delete_items", { paths });
+/** 失敗時は OpError（ゴミ箱が使えない場合は trashUnavailable）で reject される */
+export async function deleteItems(
+  paths: string[],
+  continueOnError?: boolean,
+  mode?: DeleteMode
+): Promise<BatchReport> {
+  return invoke<BatchReport>("delete_items", {
+    paths,
+    continueOnError: continueOnError ?? null,
+    mode: mode ?? null,
+  });
 }
 
+/** 失敗時は OpError（名前の検証エラーは invalidCharacter などの種類付き）で reject される */
 export async function renameItem(
   path: string,
   newName: string
@@ -39,6 +103,52 @@ export async function renameItem(
   return invoke<string>("rename_item", { path, newName });
 }
 
+/** preview なら名前を変更せず、新しいパス（destination）と衝突（failed）だけを返す */
+export async function batchRename(
+  paths: string[],
+  rules: RenameRules,
+  preview?: boolean,
+  continueOnError?: boolean
+): Promise<BatchReport> {
+  return invoke<BatchReport>("batch_rename", {
+    paths,
+    rules,
+    preview: preview ?? null,
+    continueOnError: continueOnError ?? null,
+  });
+}
+
+/** パーミッションを変更し、変更対象（recursive なら配下も）ごとの結果を返す */
+export async function setPermissions(
+  paths: string[],
+  rules: PermissionRules,
+  continueOnError?: boolean
+): Promise<BatchReport> {
+  return invoke<BatchReport>("set_permissions", {
+    paths,
+    rules,
+    continueOnError: continueOnError ?? null,
+  });
+}
+
+/** 所有者・グループを変更する。名前か数値 ID で指定し、省略した方は変えない */
+export async function setOwner(
+  paths: string[],
+  owner?: string,
+  group?: string,
+  recursive?: boolean,
+  continueOnError?: boolean
+): Promise<BatchReport> {
+  return invoke<BatchReport>("set_owner", {
+    paths,
+    owner: owner ?? null,
+    group: group ?? null,
+    recursive: recursive ?? null,
+    continueOnError: continueOnError ?? null,
+  });
+}
+
+/** 失敗時は renameItem と同じく OpError で reject される */
 export async function createDirectory(
   path: string,
   name: string
@@ -46,12 +156,74 @@ export async function createDirectory(
   return invoke<string>("create_directory", { path, name });
 }
 
+/** 空のファイルを作る。失敗時は OpError で reject される */
+export async function createFile(path: string, name: string): Promise<string> {
+  return invoke<string>("create_file", { path, name });
+}
+
+/** 「新規作成」に出すテンプレート。未指定なら OS のテンプレートフォルダ */
+export async function listTemplates(
+  templatesDir?: string
+): Promise<FileEntry[]> {
+  return invoke<FileEntry[]>("list_templates", {
+    templatesDir: templatesDir || null,
+  });
+}
+
+/** シンボリックリンクを作る。relative なら作成先からの相対パスでリンクする */
+export async function createSymlink(
+  target: string,
+  destination: string,
+  name?: string,
+  relative?: boolean
+): Promise<FileEntry> {
+  return invoke<FileEntry>("create_symlink", {
+    target,
+    destination,
+    name: name ?? null,
+    relative: relative ?? null,
+  });
+}
+
+/** ハードリンクを作る（ファイルのみ、同じボリューム内のみ） */
+export async function createHardlink(
+  target: string,
+  destination: string,
+  name?: string
+): Promise<FileEntry> {
+  return invoke<FileEntry>("create_hardlink", {
+    target,
+    destination,
+    name: name ?? null,
+  });
+}
+
+/** テンプレートをコピーして作成したパスを返す */
+export async function createFromTemplate(
+  template: string,
+  destination: string,
+  name?: string
+): Promise<string> {
+  return invoke<string>("create_from_template", {
+    template,
+    destination,
+    name: name ?? null,
+  });
+}
+
+/** 名前に query を含む項目を名前の自然順で返す。foldKana ならかなと全角・半角の違いを無視する */
 export async function searchFiles(
   path: string,
   query: string,
-  maxResults?: number
+  maxResults?: number,
+  foldKana?: boolean
 ): Promise<FileEntry[]> {
-  return invoke<FileEntry[]>("search_files", { path, query, maxResults });
+  return invoke<FileEntry[]>("search_files", {
+    path,
+    query,
+    maxResults,
+    foldKana: foldKana ?? null,
+  });
 }
 
 export async function readFilePreview(
@@ -61,6 +233,11 @@ export async function readFilePreview(
   return invoke<string>("read_file_preview", { path, maxBytes });
 }
 
+/** 失敗時は OpError で reject される */
+export async function getFileProperties(path: string): Promise<FileProperties> {
+  return invoke<FileProperties>("get_file_properties", { path });
+}
+
 export async function openFile(path: string): Promise<void> {
   return open(path);
 }
@@ -68,14 +245,34 @@ export async function openFile(path: string): Promise<void> {
 export async function checkCopyConflicts(
   sources: string[],
   destination: string
-): Promise<string[]> {
-  return invoke<string[]>("check_copy_conflicts", { sources, destination });
+): Promise<CopyConflict[]> {
+  return invoke<CopyConflict[]>("check_copy_conflicts", {
+    sources,
+    destination,
+  });
 }
 
 export async function copyItemsWithStrategy(
   sources: string[],
   destination: string,
-  strategy: string
-): Promise<void> {
-  return invoke("copy_items_with_strategy", { sources, destination, strategy });
+  strategy: string,
+  options?: TransferOptions,
+  operationId?: string
+): Promise<BatchReport> {
+  return invoke<BatchReport>("copy_items_with_strategy", {
+    sources,
+    destination,
+    strategy,
+    options: options ?? null,
+    operationId: operationId ?? null,
+  });
+}
+
+/** コピー・移動の進捗イベントを購読 */
+export async function onTransferProgress(
+  handler: (progress: TransferProgress) => void
+): Promise<UnlistenFn> {
+  return listen<TransferProgress>("file_op_progress", (event) =>
+    handler(event.payload)
+  );
 }
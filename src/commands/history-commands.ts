@@ -0,0 +1,14 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { HistoryEntry, HistoryView } from "../types";
+
+export async function undoLastOperation(): Promise<HistoryEntry> {
+  return invoke<HistoryEntry>("undo_last_operation");
+}
+
+export async function redoOperation(): Promise<HistoryEntry> {
+  return invoke<HistoryEntry>("redo_operation");
+}
+
+export async function listOperationHistory(): Promise<HistoryView> {
+  return invoke<HistoryView>("list_operation_history");
+}
@@ -0,0 +1,83 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { DeleteMode, InterruptedJob, JobInfo, TransferOptions } from "../types";
+
+export async function submitCopyJob(
+  sources: string[],
+  destination: string,
+  strategy?: string,
+  options?: TransferOptions
+): Promise<number> {
+  return invoke<number>("submit_copy_job", {
+    sources,
+    destination,
+    strategy: strategy ?? null,
+    options: options ?? null,
+  });
+}
+
+export async function submitMoveJob(
+  sources: string[],
+  destination: string,
+  strategy?: string,
+  options?: TransferOptions
+): Promise<number> {
+  return invoke<number>("submit_move_job", {
+    sources,
+    destination,
+    strategy: strategy ?? null,
+    options: options ?? null,
+  });
+}
+
+export async function submitDeleteJob(
+  paths: string[],
+  continueOnError?: boolean,
+  mode?: DeleteMode
+): Promise<number> {
+  return invoke<number>("submit_delete_job", {
+    paths,
+    continueOnError: continueOnError ?? null,
+    mode: mode ?? null,
+  });
+}
+
+export async function listJobs(): Promise<JobInfo[]> {
+  return invoke<JobInfo[]>("list_jobs");
+}
+
+export async function pauseJob(jobId: number): Promise<JobInfo> {
+  return invoke<JobInfo>("pause_job", { jobId });
+}
+
+export async function resumeJob(jobId: number): Promise<JobInfo> {
+  return invoke<JobInfo>("resume_job", { jobId });
+}
+
+export async function cancelJob(jobId: number): Promise<JobInfo> {
+  return invoke<JobInfo>("cancel_job", { jobId });
+}
+
+export async function clearFinishedJobs(): Promise<void> {
+  return invoke("clear_finished_jobs");
+}
+
+/** 前回の起動時に中断したコピージョブ */
+export async function listInterruptedJobs(): Promise<InterruptedJob[]> {
+  return invoke<InterruptedJob[]>("list_interrupted_jobs");
+}
+
+export async function resumeInterruptedJob(journalId: string): Promise<number> {
+  return invoke<number>("resume_interrupted_job", { journalId });
+}
+
+export async function discardInterruptedJob(journalId: string): Promise<void> {
+  return invoke("discard_interrupted_job", { journalId });
+}
+
+/** ジョブの状態変化・進捗イベントを購読 */
+export async function onJobUpdated(
+  handler: (job: JobInfo) => void
+): Promise<UnlistenFn> {
+  return listen<JobInfo>("job_updated", (event) => handler(event.payload));
+}
@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { BatchReport, TrashEntry } from "../types";
+
+export async function listTrash(): Promise<TrashEntry[]> {
+  return invoke<TrashEntry[]>("list_trash");
+}
+
+export async function restoreTrashItems(
+  ids: string[],
+  strategy?: string,
+  continueOnError?: boolean
+): Promise<BatchReport> {
+  return invoke<BatchReport>("restore_trash_items", {
+    ids,
+    strategy: strategy ?? null,
+    continueOnError: continueOnError ?? null,
+  });
+}
+
+export async function purgeTrashItems(ids: string[]): Promise<void> {
+  return invoke<void>("purge_trash_items", { ids });
+}
+
+export async function emptyTrash(): Promise<void> {
+  return invoke<void>("empty_trash");
+}
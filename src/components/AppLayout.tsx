@@ -20,6 +20,8 @@ import { createContextMenuHandlers } from "../utils/context-menu-handlers";
 import { useKeyboardShortcuts } from "../hooks/use-keyboard-shortcuts";
 import { useMouseNavigation } from "../hooks/use-mouse-navigation";
 import { useOsDrop } from "../hooks/use-os-drop";
+import { useInterruptedJobs } from "../hooks/use-interrupted-jobs";
+import { useDateFormatSync } from "../hooks/use-date-format-sync";
 import { TabBar } from "./TabBar";
 import { Toolbar } from "./Toolbar";
 import { Sidebar } from "./Sidebar";
@@ -98,6 +100,8 @@ export function AppLayout() {
 
   useMouseNavigation();
   const { isDraggingOver } = useOsDrop();
+  useInterruptedJobs();
+  useDateFormatSync();
 
   // アクティブタブのパスを取得（ターミナルの cwd に使用）
   const activeTabPath = useTabStore((s) => {
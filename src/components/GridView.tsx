@@ -1,10 +1,8 @@
-import { useMemo, useCallback } from "react";
+import { useCallback } from "react";
 import { VirtuosoGrid } from "react-virtuoso";
 import { useFileStore } from "../stores/file-store";
-import { useUIStore } from "../stores/ui-store";
 import { useClipboardStore } from "../stores/clipboard-store";
 import { useNavigation } from "../hooks/use-navigation";
-import { sortEntries } from "../utils/sort";
 import { isCutPath } from "../utils/clipboard-helpers";
 import { FileCard } from "./FileCard";
 import type { FileEntry } from "../types";
@@ -16,8 +14,6 @@ interface GridViewProps {
 
 export function GridView({ onContextMenu, onFileOpen }: GridViewProps) {
   const entries = useFileStore((s) => s.entries);
-  const sortConfig = useFileStore((s) => s.sortConfig);
-  const showHidden = useUIStore((s) => s.showHidden);
   const selectedPaths = useFileStore((s) => s.selectedPaths);
   const setSelectedPaths = useFileStore((s) => s.setSelectedPaths);
   const toggleSelection = useFileStore((s) => s.toggleSelection);
@@ -25,16 +21,6 @@ export function GridView({ onContextMenu, onFileOpen }: GridViewProps) {
   const clipboardMode = useClipboardStore((s) => s.mode);
   const { navigateTo } = useNavigation();
 
-  const sortedEntries = useMemo(
-    () => sortEntries(entries, sortConfig),
-    [entries, sortConfig]
-  );
-
-  const visibleEntries = useMemo(
-    () => (showHidden ? sortedEntries : sortedEntries.filter((e) => !e.isHidden)),
-    [sortedEntries, showHidden]
-  );
-
   const handleSelect = useCallback(
     (entry: FileEntry, e: React.MouseEvent) => {
       if (e.ctrlKey || e.metaKey) {
@@ -74,7 +60,7 @@ export function GridView({ onContextMenu, onFileOpen }: GridViewProps) {
     <div className="h-full relative" onContextMenu={onContextMenu}>
       <div className="absolute inset-0 overflow-hidden">
         <VirtuosoGrid
-          data={visibleEntries}
+          data={entries}
           listClassName="grid grid-cols-[repeat(auto-fill,minmax(100px,1fr))] gap-1 p-2"
           itemContent={itemContent}
         />
@@ -1,14 +1,12 @@
-import { useMemo, useCallback, useRef, useEffect } from "react";
+import { useCallback, useRef, useEffect } from "react";
 import type { VirtuosoHandle } from "react-virtuoso";
 import { useTranslation } from "react-i18next";
 import { Virtuoso } from "react-virtuoso";
 import { useFileStore } from "../stores/file-store";
-import { useUIStore } from "../stores/ui-store";
 import { useTabStore } from "../stores/tab-store";
 import { useClipboardStore } from "../stores/clipboard-store";
 import { useDragDrop } from "../hooks/use-drag-drop";
 import { useNavigation } from "../hooks/use-navigation";
-import { sortEntries } from "../utils/sort";
 import { isCutPath } from "../utils/clipboard-helpers";
 import { FileRow } from "./FileRow";
 import type { FileEntry, SortKey } from "../types";
@@ -28,7 +26,6 @@ export function ListView({ onContextMenu, onFileOpen }: ListViewProps) {
   const { t } = useTranslation();
   const entries = useFileStore((s) => s.entries);
   const sortConfig = useFileStore((s) => s.sortConfig);
-  const showHidden = useUIStore((s) => s.showHidden);
   const selectedPaths = useFileStore((s) => s.selectedPaths);
   const setSelectedPaths = useFileStore((s) => s.setSelectedPaths);
   const toggleSelection = useFileStore((s) => s.toggleSelection);
@@ -42,27 +39,17 @@ export function ListView({ onContextMenu, onFileOpen }: ListViewProps) {
   const focusedIndex = useFileStore((s) => s.focusedIndex);
   const virtuosoRef = useRef<VirtuosoHandle>(null);
 
-  const sortedEntries = useMemo(
-    () => sortEntries(entries, sortConfig),
-    [entries, sortConfig]
-  );
-
-  const visibleEntries = useMemo(
-    () => (showHidden ? sortedEntries : sortedEntries.filter((e) => !e.isHidden)),
-    [sortedEntries, showHidden]
-  );
-
   const handleSelect = useCallback(
     (entry: FileEntry, e: React.MouseEvent) => {
       if (e.ctrlKey || e.metaKey) {
         toggleSelection(entry.path);
       } else if (e.shiftKey) {
-        selectRange(visibleEntries, entry.path);
+        selectRange(entries, entry.path);
       } else {
         setSelectedPaths(new Set([entry.path]));
       }
     },
-    [toggleSelection, selectRange, setSelectedPaths, visibleEntries]
+    [toggleSelection, selectRange, setSelectedPaths, entries]
   );
 
   const handleOpen = useCallback(
@@ -149,7 +136,7 @@ export function ListView({ onContextMenu, onFileOpen }: ListViewProps) {
         <div className="absolute inset-0 overflow-hidden">
           <Virtuoso
             ref={virtuosoRef}
-            data={visibleEntries}
+            data={entries}
             itemContent={itemContent}
           />
         </div>
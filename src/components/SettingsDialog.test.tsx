@@ -51,6 +51,12 @@ describe("SettingsDialog", () => {
     expect(useUIStore.getState().viewMode).toBe("grid");
   });
 
+  it("日時の表示形式を切り替えられる", () => {
+    render(<SettingsDialog open={true} onClose={onClose} />);
+    fireEvent.click(screen.getByRole("radio", { name: /relative/i }));
+    expect(useUIStore.getState().dateStyle).toBe("relative");
+  });
+
   it("閉じるボタンでonCloseが呼ばれる", () => {
     render(<SettingsDialog open={true} onClose={onClose} />);
     const closeBtn = screen.getByTitle("Close");
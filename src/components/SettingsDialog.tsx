@@ -14,6 +14,8 @@ import {
   Info,
   Download,
   RefreshCw,
+  Calendar,
+  Clock,
 } from "lucide-react";
 import type { Language, Theme, ThemeId } from "../types";
 import { loadAllThemes } from "../themes";
@@ -47,6 +49,8 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
   const setWindowTransparency = useUIStore((s) => s.setWindowTransparency);
   const windowOpacity = useUIStore((s) => s.windowOpacity);
   const setWindowOpacity = useUIStore((s) => s.setWindowOpacity);
+  const dateStyle = useUIStore((s) => s.dateStyle);
+  const setDateStyle = useUIStore((s) => s.setDateStyle);
   const showSplash = useUIStore((s) => s.showSplash);
   const setShowSplash = useUIStore((s) => s.setShowSplash);
   const dialogRef = useRef<HTMLDivElement>(null);
@@ -202,6 +206,32 @@ export function SettingsDialog({ open, onClose }: SettingsDialogProps) {
                   </SegmentedControl>
                 </SettingRow>
 
+                <SettingRow
+                  label={t("settings.dateStyle")}
+                  description={t("settings.dateStyleDesc")}
+                >
+                  <SegmentedControl>
+                    <SegmentedButton
+                      label={t("settings.dateStyleIso")}
+                      icon={<Calendar size={15} />}
+                      active={dateStyle === "iso"}
+                      onClick={() => setDateStyle("iso")}
+                    />
+                    <SegmentedButton
+                      label={t("settings.dateStyleLocale")}
+                      icon={<Globe size={15} />}
+                      active={dateStyle === "locale"}
+                      onClick={() => setDateStyle("locale")}
+                    />
+                    <SegmentedButton
+                      label={t("settings.dateStyleRelative")}
+                      icon={<Clock size={15} />}
+                      active={dateStyle === "relative"}
+                      onClick={() => setDateStyle("relative")}
+                    />
+                  </SegmentedControl>
+                </SettingRow>
+
                 <SettingRow
                   label={t("settings.windowTransparency")}
                   description={t("settings.windowTransparencyDesc")}
@@ -0,0 +1,17 @@
+import { useEffect } from "react";
+import { useUIStore } from "../stores/ui-store";
+import { setDateFormat } from "../commands/format-commands";
+import { useNavigation } from "./use-navigation";
+
+/** 日時の表示形式と言語を Rust 側へ伝え、表示中の一覧を読み直す */
+export function useDateFormatSync() {
+  const dateStyle = useUIStore((s) => s.dateStyle);
+  const language = useUIStore((s) => s.language);
+  const { refresh } = useNavigation();
+
+  useEffect(() => {
+    setDateFormat(dateStyle, language)
+      .then(refresh)
+      .catch((err) => console.error("Date format sync failed:", err));
+  }, [dateStyle, language, refresh]);
+}
@@ -0,0 +1,45 @@
+import { useEffect } from "react";
+import { useTranslation } from "react-i18next";
+import { ask } from "@tauri-apps/plugin-dialog";
+import {
+  listInterruptedJobs,
+  resumeInterruptedJob,
+  discardInterruptedJob,
+} from "../commands/job-commands";
+
+/** 起動時に前回中断したコピーを見つけたら、再開するか確認する */
+export function useInterruptedJobs() {
+  const { t } = useTranslation();
+
+  useEffect(() => {
+    let cancelled = false;
+
+    (async () => {
+      const jobs = await listInterruptedJobs();
+      for (const job of jobs) {
+        if (cancelled) return;
+        const resume = await ask(
+          t("resume.message", {
+            count: job.sources.length,
+            destination: job.destination,
+            done: job.filesDone,
+          }),
+          {
+            title: t("resume.title"),
+            okLabel: t("resume.resume"),
+            cancelLabel: t("resume.discard"),
+          }
+        );
+        if (resume) {
+          await resumeInterruptedJob(job.id);
+        } else {
+          await discardInterruptedJob(job.id);
+        }
+      }
+    })().catch((err) => console.error("Resume check failed:", err));
+
+    return () => {
+      cancelled = true;
+    };
+  }, [t]);
+}
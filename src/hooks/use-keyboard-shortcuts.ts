@@ -9,6 +9,7 @@ import {
   readClipboardFiles,
   writeClipboardFiles,
 } from "../commands/clipboard-commands";
+import { redoOperation, undoLastOperation } from "../commands/history-commands";
 import { pasteWithConflictCheck, type PasteResult } from "../utils/paste-with-conflicts";
 
 interface ShortcutActions {
@@ -21,7 +22,6 @@ interface ShortcutActions {
 }
 
 import type { FileEntry } from "../types";
-import { sortEntries } from "../utils/sort";
 
 export function useKeyboardShortcuts(actions: ShortcutActions) {
   const addTab = useTabStore((s) => s.addTab);
@@ -106,13 +106,32 @@ export function useKeyboardShortcuts(actions: ShortcutActions) {
         }
         return;
       }
+      // Ctrl+Z: Undo / Ctrl+Y, Ctrl+Shift+Z: Redo
+      if (ctrl && key === "z") {
+        e.preventDefault();
+        try {
+          await (shift ? redoOperation() : undoLastOperation());
+          refresh();
+        } catch (err) {
+          console.error("Undo failed:", err);
+        }
+        return;
+      }
+      if (ctrl && !shift && key === "y") {
+        e.preventDefault();
+        try {
+          await redoOperation();
+          refresh();
+        } catch (err) {
+          console.error("Redo failed:", err);
+        }
+        return;
+      }
       // Arrow key navigation
       if (!ctrl && !alt && (key === "arrowdown" || key === "arrowup" || key === "home" || key === "end")) {
         e.preventDefault();
-        const { entries, sortConfig, focusedIndex } = useFileStore.getState();
-        const showHidden = useUIStore.getState().showHidden;
-        const sorted = sortEntries(entries, sortConfig);
-        const visible = showHidden ? sorted : sorted.filter((en) => !en.isHidden);
+        // 並べ替え・隠しファイルの絞り込みは読み込み時に済んでいる
+        const { entries: visible, focusedIndex } = useFileStore.getState();
         if (visible.length === 0) return;
 
         let newIndex = focusedIndex;
@@ -150,9 +169,8 @@ export function useKeyboardShortcuts(actions: ShortcutActions) {
         e.preventDefault();
         const paths = Array.from(selectedPaths);
         if (paths.length !== 1) return;
-        const { entries, sortConfig } = useFileStore.getState();
-        const sorted = sortEntries(entries, sortConfig);
-        const entry = sorted.find((en) => en.path === paths[0]);
+        const { entries } = useFileStore.getState();
+        const entry = entries.find((en) => en.path === paths[0]);
         if (entry) actions.onFileOpen(entry);
         return;
       }
@@ -13,6 +13,11 @@ export const en = {
   "settings.viewModeDesc": "Switch the file list display method",
   "settings.viewList": "List",
   "settings.viewGrid": "Grid",
+  "settings.dateStyle": "Date format",
+  "settings.dateStyleDesc": "Choose how modification dates are shown",
+  "settings.dateStyleIso": "ISO",
+  "settings.dateStyleLocale": "Locale",
+  "settings.dateStyleRelative": "Relative",
   "settings.language": "Language",
   "settings.languageDesc": "Select the display language",
 
@@ -60,6 +65,12 @@ export const en = {
   "conflict.rename": "Keep Both",
   "conflict.cancel": "Cancel",
 
+  // Interrupted copy prompt
+  "resume.title": "Interrupted Copy",
+  "resume.message": "A copy of {{count}} item(s) to {{destination}} was interrupted ({{done}} file(s) done). Resume where it left off?",
+  "resume.resume": "Resume",
+  "resume.discard": "Discard",
+
   // NewFolderDialog
   "newFolder.title": "New Folder",
   "newFolder.placeholder": "Folder name",
@@ -13,6 +13,11 @@ export const ja = {
   "settings.viewModeDesc": "ファイル一覧の表示方法を切り替えます",
   "settings.viewList": "リスト",
   "settings.viewGrid": "グリッド",
+  "settings.dateStyle": "日時の表示",
+  "settings.dateStyleDesc": "更新日時などの表示形式を切り替えます",
+  "settings.dateStyleIso": "ISO",
+  "settings.dateStyleLocale": "地域",
+  "settings.dateStyleRelative": "相対",
   "settings.language": "言語",
   "settings.languageDesc": "表示言語を選択します",
 
@@ -60,6 +65,12 @@ export const ja = {
   "conflict.rename": "両方残す",
   "conflict.cancel": "キャンセル",
 
+  // Interrupted copy prompt
+  "resume.title": "中断したコピー",
+  "resume.message": "前回中断したコピー（{{count}} 項目 → {{destination}}、{{done}} ファイル完了済み）があります。続きから再開しますか？",
+  "resume.resume": "再開",
+  "resume.discard": "破棄",
+
   // NewFolderDialog
   "newFolder.title": "新しいフォルダ",
   "newFolder.placeholder": "フォルダ名",
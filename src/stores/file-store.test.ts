@@ -2,13 +2,33 @@ import { describe, it, expect, beforeEach, vi } from "vitest";
 import type { FileEntry } from "../types";
 
 vi.mock("../commands/fs-commands", () => ({
-  readDirectory: vi.fn(),
+  readDirectoryStream: vi.fn(),
+  cancelDirectoryStream: vi.fn(() => Promise.resolve()),
 }));
 
 import { useFileStore } from "./file-store";
-import { readDirectory } from "../commands/fs-commands";
+import { useUIStore } from "./ui-store";
+import {
+  readDirectoryStream,
+  cancelDirectoryStream,
+} from "../commands/fs-commands";
+import type { DirectoryEvent } from "../types";
 
-const mockReadDirectory = vi.mocked(readDirectory);
+const mockReadDirectoryStream = vi.mocked(readDirectoryStream);
+const mockCancelDirectoryStream = vi.mocked(cancelDirectoryStream);
+
+/** 渡したバッチを順に送り、最後に done を送るストリーム */
+function streamOf(...batches: FileEntry[][]) {
+  return async (_path: string, onEvent: (event: DirectoryEvent) => void) => {
+    let total = 0;
+    for (const entries of batches) {
+      onEvent({ type: "entries", entries });
+      total += entries.length;
+    }
+    onEvent({ type: "done", total });
+    return 1;
+  };
+}
 
 function makeEntry(overrides: Partial<FileEntry> = {}): FileEntry {
   return {
@@ -19,6 +39,9 @@ function makeEntry(overrides: Partial<FileEntry> = {}): FileEntry {
     isHidden: false,
     size: 100,
     modified: "2024-01-01 00:00",
+    modifiedAt: null,
+    createdAt: null,
+    accessedAt: null,
     mimeType: "text/plain",
     ...overrides,
   };
@@ -40,7 +63,7 @@ describe("fileStore", () => {
   describe("loadDirectory", () => {
     it("loading状態を遷移する", async () => {
       const entries = [makeEntry()];
-      mockReadDirectory.mockResolvedValue(entries);
+      mockReadDirectoryStream.mockImplementation(streamOf(entries));
 
       const promise = useFileStore.getState().loadDirectory("/home");
       expect(useFileStore.getState().loading).toBe(true);
@@ -52,14 +75,14 @@ describe("fileStore", () => {
 
     it("entriesを設定する", async () => {
       const entries = [makeEntry({ name: "a.txt", path: "/a.txt" })];
-      mockReadDirectory.mockResolvedValue(entries);
+      mockReadDirectoryStream.mockImplementation(streamOf(entries));
 
       await useFileStore.getState().loadDirectory("/home");
       expect(useFileStore.getState().entries).toEqual(entries);
     });
 
     it("エラー時はerrorを設定する", async () => {
-      mockReadDirectory.mockRejectedValue(new Error("permission denied"));
+      mockReadDirectoryStream.mockRejectedValue(new Error("permission denied"));
 
       await useFileStore.getState().loadDirectory("/root");
       expect(useFileStore.getState().error).toBe("Error: permission denied");
@@ -72,12 +95,58 @@ describe("fileStore", () => {
         selectedPaths: new Set(["/old"]),
         lastSelectedPath: "/old",
       });
-      mockReadDirectory.mockResolvedValue([]);
+      mockReadDirectoryStream.mockImplementation(streamOf());
 
       await useFileStore.getState().loadDirectory("/home");
       expect(useFileStore.getState().selectedPaths.size).toBe(0);
       expect(useFileStore.getState().lastSelectedPath).toBeNull();
     });
+
+    it("バッチを順に追加する", async () => {
+      const first = [makeEntry({ name: "a.txt", path: "/a.txt" })];
+      const second = [makeEntry({ name: "b.txt", path: "/b.txt" })];
+      mockReadDirectoryStream.mockImplementation(streamOf(first, second));
+
+      await useFileStore.getState().loadDirectory("/home");
+      expect(useFileStore.getState().entries).toEqual([...first, ...second]);
+    });
+
+    it("空のフォルダでは一覧を空にする", async () => {
+      useFileStore.setState({ entries: [makeEntry()] });
+      mockReadDirectoryStream.mockImplementation(streamOf());
+
+      await useFileStore.getState().loadDirectory("/empty");
+      expect(useFileStore.getState().entries).toEqual([]);
+      expect(useFileStore.getState().loading).toBe(false);
+    });
+
+    it("読み込み中に移動すると前のストリームを止める", async () => {
+      // done を送らない（読み込み中のまま）ストリーム
+      mockReadDirectoryStream.mockImplementation(async (_path, onEvent) => {
+        onEvent({ type: "entries", entries: [makeEntry()] });
+        return 7;
+      });
+      await useFileStore.getState().loadDirectory("/huge");
+
+      mockReadDirectoryStream.mockImplementation(streamOf());
+      await useFileStore.getState().loadDirectory("/home");
+      expect(mockCancelDirectoryStream).toHaveBeenCalledWith(7);
+    });
+
+    it("並べ替えと隠しファイルの設定を ListOptions で渡す", async () => {
+      mockReadDirectoryStream.mockImplementation(streamOf());
+      useUIStore.setState({ showHidden: true });
+      useFileStore.setState({ sortConfig: { key: "size", order: "desc" } });
+
+      await useFileStore.getState().loadDirectory("/home");
+      expect(mockReadDirectoryStream).toHaveBeenLastCalledWith("/home", expect.any(Function), {
+        sortBy: "size",
+        descending: true,
+        dirsFirst: true,
+        showHidden: true,
+      });
+      useUIStore.setState({ showHidden: false });
+    });
   });
 
   describe("toggleSelection", () => {
@@ -169,5 +238,19 @@ describe("fileStore", () => {
         order: "desc",
       });
     });
+
+    it("選択を保ったまま新しい順で読み込み直す", async () => {
+      mockReadDirectoryStream.mockImplementation(streamOf());
+      await useFileStore.getState().loadDirectory("/home");
+      useFileStore.getState().setSelectedPaths(new Set(["/home/a.txt"]));
+
+      useFileStore.getState().setSortConfig({ key: "modified", order: "asc" });
+      expect(mockReadDirectoryStream).toHaveBeenLastCalledWith(
+        "/home",
+        expect.any(Function),
+        expect.objectContaining({ sortBy: "modified", descending: false }),
+      );
+      expect(useFileStore.getState().selectedPaths).toEqual(new Set(["/home/a.txt"]));
+    });
   });
 });
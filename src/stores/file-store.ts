@@ -1,8 +1,14 @@
 import { create } from "zustand";
 import type { FileEntry, SortConfig } from "../types";
-import { readDirectory } from "../commands/fs-commands";
+import { readDirectoryStream, cancelDirectoryStream } from "../commands/fs-commands";
+import { toListOptions } from "../utils/sort";
+import { useUIStore } from "./ui-store";
 
 let loadGeneration = 0;
+/** 読み込み中のフォルダのストリーム ID（移動したら止める） */
+let activeStream: number | null = null;
+/** 表示中のフォルダ（並べ替えや隠しファイルの表示を変えたら読み込み直す） */
+let currentPath: string | null = null;
 
 interface FileStore {
   entries: FileEntry[];
@@ -14,6 +20,8 @@ interface FileStore {
   error: string | null;
 
   loadDirectory: (path: string) => Promise<void>;
+  /** 表示中のフォルダを選択を保ったまま読み込み直す */
+  reload: () => Promise<void>;
   setSelectedPaths: (paths: Set<string>) => void;
   toggleSelection: (path: string) => void;
   selectRange: (entries: FileEntry[], targetPath: string) => void;
@@ -23,6 +31,53 @@ interface FileStore {
   setFocusedIndex: (index: number) => void;
 }
 
+/** 並べ替え・絞り込みの設定を付けてフォルダを読み込み、届いたバッチを一覧に入れていく */
+async function streamDirectory(path: string) {
+  const gen = ++loadGeneration;
+  currentPath = path;
+  if (activeStream !== null) {
+    cancelDirectoryStream(activeStream).catch(console.error);
+    activeStream = null;
+  }
+  const set = useFileStore.setState;
+  const options = toListOptions(
+    useFileStore.getState().sortConfig,
+    useUIStore.getState().showHidden,
+  );
+  let received = false;
+  let finished = false;
+  try {
+    const streamId = await readDirectoryStream(
+      path,
+      (event) => {
+        // 古いリクエストの結果は無視
+        if (gen !== loadGeneration) return;
+        if (event.type === "entries") {
+          // 最初のバッチで一覧を置き換え、以降は後ろに足していく
+          set((s) => ({
+            entries: received ? s.entries.concat(event.entries) : event.entries,
+            loading: false,
+          }));
+          received = true;
+        } else {
+          finished = true;
+          activeStream = null;
+          if (!received) set({ entries: [], loading: false });
+        }
+      },
+      options
+    );
+    if (gen !== loadGeneration) {
+      cancelDirectoryStream(streamId).catch(console.error);
+      return;
+    }
+    if (!finished) activeStream = streamId;
+  } catch (e) {
+    if (gen !== loadGeneration) return;
+    set({ entries: [], loading: false, error: String(e) });
+  }
+}
+
 export const useFileStore = create<FileStore>((set, get) => ({
   entries: [],
   selectedPaths: new Set(),
@@ -33,17 +88,15 @@ export const useFileStore = create<FileStore>((set, get) => ({
   error: null,
 
   loadDirectory: async (path) => {
-    const gen = ++loadGeneration;
     set({ loading: true, error: null, selectedPaths: new Set(), lastSelectedPath: null, focusedIndex: -1 });
-    try {
-      const entries = await readDirectory(path);
-      // 古いリクエストの結果は無視
-      if (gen !== loadGeneration) return;
-      set({ entries, loading: false });
-    } catch (e) {
-      if (gen !== loadGeneration) return;
-      set({ entries: [], loading: false, error: String(e) });
-    }
+    await streamDirectory(path);
+  },
+
+  reload: async () => {
+    if (currentPath === null) return;
+    // 並びが変わるので、選択はパスで保ちフォーカス位置だけ外す
+    set({ error: null, focusedIndex: -1 });
+    await streamDirectory(currentPath);
   },
 
   setSelectedPaths: (paths) => {
@@ -88,5 +141,13 @@ export const useFileStore = create<FileStore>((set, get) => ({
 
   setFocusedIndex: (index) => set({ focusedIndex: index }),
 
-  setSortConfig: (config) => set({ sortConfig: config }),
+  setSortConfig: (config) => {
+    set({ sortConfig: config });
+    get().reload();
+  },
 }));
+
+// 隠しファイルの絞り込みも読み込み時に行うので、表示を切り替えたら読み込み直す
+useUIStore.subscribe((state, prev) => {
+  if (state.showHidden !== prev.showHidden) useFileStore.getState().reload();
+});
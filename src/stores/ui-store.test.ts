@@ -14,6 +14,7 @@ describe("uiStore", () => {
       terminalVisible: false,
       terminalShellPath: "",
       terminalFontSize: 14,
+      dateStyle: "iso",
     });
   });
 
@@ -217,4 +218,26 @@ describe("uiStore", () => {
       expect(settings.showSplash).toBe(false);
     });
   });
+
+  describe("setTemplatesDir", () => {
+    it("テンプレートフォルダを設定してlocalStorageに保存する", () => {
+      useUIStore.getState().setTemplatesDir("/home/user/Templates");
+      expect(useUIStore.getState().templatesDir).toBe("/home/user/Templates");
+      const stored = JSON.parse(localStorage.getItem(STORAGE_KEY)!);
+      expect(stored.templatesDir).toBe("/home/user/Templates");
+    });
+  });
+
+  describe("setDateStyle", () => {
+    it("初期値はiso", () => {
+      expect(useUIStore.getState().dateStyle).toBe("iso");
+    });
+
+    it("日時の表示形式を設定してlocalStorageに保存する", () => {
+      useUIStore.getState().setDateStyle("relative");
+      expect(useUIStore.getState().dateStyle).toBe("relative");
+      const stored = JSON.parse(localStorage.getItem(STORAGE_KEY)!);
+      expect(stored.dateStyle).toBe("relative");
+    });
+  });
 });
@@ -1,6 +1,6 @@
 import { create } from "zustand";
 import i18n from "i18next";
-import type { ViewMode, Language, ThemeId } from "../types";
+import type { ViewMode, Language, ThemeId, DateStyle } from "../types";
 import { getTheme, loadTheme } from "../themes";
 import { applyTheme } from "../themes/apply-theme";
 
@@ -17,6 +17,8 @@ interface UISettings {
   windowTransparency: boolean;
   windowOpacity: number;
   showSplash: boolean;
+  templatesDir: string;
+  dateStyle: DateStyle;
 }
 
 interface UIStore extends UISettings {
@@ -32,6 +34,8 @@ interface UIStore extends UISettings {
   setWindowTransparency: (enabled: boolean) => void;
   setWindowOpacity: (opacity: number) => void;
   setShowSplash: (show: boolean) => void;
+  setTemplatesDir: (dir: string) => void;
+  setDateStyle: (style: DateStyle) => void;
 }
 
 const STORAGE_KEY = "tauri-filer-ui-settings";
@@ -62,6 +66,8 @@ const defaults: UISettings = {
   windowTransparency: false,
   windowOpacity: 80,
   showSplash: true,
+  templatesDir: "",
+  dateStyle: "iso",
 };
 
 const initial: UISettings = { ...defaults, ...loadSettings() };
@@ -80,6 +86,8 @@ function getSettings(state: UIStore): UISettings {
     windowTransparency: state.windowTransparency,
     windowOpacity: state.windowOpacity,
     showSplash: state.showSplash,
+    templatesDir: state.templatesDir,
+    dateStyle: state.dateStyle,
   };
 }
 
@@ -174,4 +182,12 @@ export const useUIStore = create<UIStore>((set, get) => ({
     set({ showSplash: show });
     saveSettings(getSettings(get()));
   },
+  setTemplatesDir: (dir) => {
+    set({ templatesDir: dir });
+    saveSettings(getSettings(get()));
+  },
+  setDateStyle: (style) => {
+    set({ dateStyle: style });
+    saveSettings(getSettings(get()));
+  },
 }));
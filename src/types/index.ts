@@ -5,15 +5,220 @@ export interface FileEntry {
   isSymlink: boolean;
   isHidden: boolean;
   size: number;
+  /** 表示用の更新日時（形式は設定の dateStyle に従う） */
   modified: string | null;
+  /** 以下 3 つは UNIX エポックからのミリ秒 */
+  modifiedAt: number | null;
+  createdAt: number | null;
+  accessedAt: number | null;
   mimeType: string | null;
+  /** ListOptions.groupBy を指定したときのグループのキー */
+  group?: string | null;
 }
 
-export type SortKey = "name" | "size" | "modified";
+export type SortKey = "name" | "size" | "modified" | "type";
+
+/**
+ * グループ分けの単位。type は "folder" か小文字の拡張子（なければ ""）、
+ * date は "today" | "yesterday" | "thisWeek" | "thisMonth" | "thisYear" | "older" | "unknown"
+ * （thisWeek は月曜日から一昨日まで、未来の日時は unknown）
+ */
+export type GroupBy = "type" | "date";
+
+/** フォルダ一覧の並べ替え・絞り込み・グループ分け。省略した項目は既定値（フォルダ優先・名前順・すべて表示） */
+export interface ListOptions {
+  sortBy?: SortKey;
+  descending?: boolean;
+  dirsFirst?: boolean;
+  showHidden?: boolean;
+  /** ファイル名の glob（大文字小文字を区別しない）。フォルダには適用しない */
+  patterns?: string[];
+  /** 残す拡張子（先頭の . は省略可）。フォルダには適用しない */
+  extensions?: string[];
+  groupBy?: GroupBy | null;
+  /** 名前の比較でひらがなとカタカナ、全角と半角を区別しない */
+  foldKana?: boolean;
+}
+
+/** readDirectoryStream で届くイベント。entries は ListOptions に従った順 */
+export type DirectoryEvent =
+  | { type: "entries"; entries: FileEntry[] }
+  | { type: "done"; total: number };
+
+/** パーミッションの変更内容。モードは "755" のような 8 進数か "u+x,go-w" のような記号表記 */
+export interface PermissionRules {
+  /** ファイル・フォルダ共通のモード */
+  mode?: string | null;
+  /** ファイルだけに使うモード（mode より優先） */
+  fileMode?: string | null;
+  /** フォルダだけに使うモード（mode より優先） */
+  dirMode?: string | null;
+  /** フォルダの中身にも適用する */
+  recursive?: boolean;
+}
+
+/** プロパティダイアログ用の詳細情報。日時は entry.modifiedAt などを使う。
+ * Unix 固有の項目は他の OS では null */
+export interface FileProperties {
+  entry: FileEntry;
+  /** パーミッションビット（setuid などを含む下位 12 ビット） */
+  mode: number | null;
+  uid: number | null;
+  gid: number | null;
+  owner: string | null;
+  group: string | null;
+  inode: number | null;
+  device: number | null;
+  hardLinks: number | null;
+  linkTarget: string | null;
+  isBrokenLink: boolean;
+  isExecutable: boolean;
+  readonly: boolean;
+}
+
+export interface TransferProgress {
+  operationId: string | null;
+  currentFile: string;
+  fileBytesCopied: number;
+  fileBytesTotal: number;
+  bytesCopied: number;
+  bytesTotal: number;
+  itemsDone: number;
+  itemsTotal: number;
+}
+
+export type ConflictDecision =
+  | "skip"
+  | "overwrite"
+  | "rename"
+  | "overwriteIfNewer"
+  | "overwriteIfSizeDiffers";
+
+export interface ConflictSide {
+  isDir: boolean;
+  size: number;
+  /** UNIX epoch ms */
+  modified: number | null;
+}
+
+export interface CopyConflict {
+  name: string;
+  source: string;
+  destination: string;
+  sourceInfo: ConflictSide;
+  destinationInfo: ConflictSide;
+}
+
+export interface TransferOptions {
+  continueOnError?: boolean;
+  /** コピー元パスごとの衝突解決 */
+  decisions?: Record<string, ConflictDecision>;
+  /** 同名フォルダを統合し、中のファイルごとに衝突を解決する */
+  mergeDirectories?: boolean;
+  /** コピー先に引き継ぐ属性（省略時はパーミッションのみ） */
+  preserve?: PreserveOptions;
+  /** シンボリックリンクの扱い（省略時はリンクのままコピー） */
+  symlinks?: SymlinkPolicy;
+  /** 書き込み後にハッシュを比較する */
+  verify?: boolean;
+}
+
+export type SymlinkPolicy = "copyAsLink" | "follow" | "skip";
+
+export interface PreserveOptions {
+  timestamps?: boolean;
+  permissions?: boolean;
+  ownership?: boolean;
+  xattrs?: boolean;
+}
+
+export interface NestedConflict {
+  path: string;
+  resolution: "skip" | "overwrite" | "rename";
+  renamedTo: string | null;
+}
+
+export type ErrorKind =
+  | "notFound"
+  | "permissionDenied"
+  | "alreadyExists"
+  | "noSpace"
+  | "crossDevice"
+  | "invalidInput"
+  | "cancelled"
+  | "checksumMismatch"
+  | "trashUnavailable"
+  | "invalidCharacter"
+  | "pathSeparator"
+  | "reservedName"
+  | "nameTooLong"
+  | "unsupported"
+  | "other";
+
+export interface OpError {
+  kind: ErrorKind;
+  message: string;
+  /** 失敗した時点で操作対象がどうなっているか */
+  state: ItemState | null;
+}
+
+export type ItemState =
+  | "sourceIntact"
+  | "partialDestination"
+  | "bothPresent"
+  | "partlyMoved";
+
+export type ItemStatus = "succeeded" | "skipped" | "failed";
+
+export interface ItemResult {
+  source: string;
+  destination: string | null;
+  status: ItemStatus;
+  error: OpError | null;
+  conflicts: NestedConflict[];
+  /** 実際に使われたコピー方法（rename だけで済んだ移動では空） */
+  copyMethods: CopyMethod[];
+}
+
+export type CopyMethod = "reflink" | "sparse" | "standard";
+
+export interface BatchReport {
+  items: ItemResult[];
+}
+
+export type JobKind = "copy" | "move" | "delete";
+export type JobStatus =
+  | "queued"
+  | "running"
+  | "paused"
+  | "completed"
+  | "failed"
+  | "cancelled";
+
+export interface JobInfo {
+  id: number;
+  kind: JobKind;
+  status: JobStatus;
+  sources: string[];
+  destination: string | null;
+  progress: TransferProgress | null;
+  /** 削除ジョブの削除方法 */
+  deleteMode: DeleteMode | null;
+  /** 終了時の項目ごとの結果。失敗・キャンセルしたジョブでは中断するまでの分 */
+  report: BatchReport | null;
+  error: string | null;
+}
+
+/** trash: ゴミ箱へ移動 / permanent: 完全に削除 / shred: 上書きしてから削除 */
+export type DeleteMode = "trash" | "permanent" | "shred";
+
 export type SortOrder = "asc" | "desc";
 export type ViewMode = "list" | "grid";
 export type Language = "ja" | "en";
 
+/** 日時の表示形式。iso: 2024-01-15 10:30 / locale: 言語に合わせた形式 / relative: 3 分前 */
+export type DateStyle = "iso" | "locale" | "relative";
+
 export interface TabState {
   id: string;
   path: string;
@@ -104,3 +309,85 @@ export interface Theme {
   colors: ThemeColors;
   preview: [string, string, string];
 }
+
+/** 前回の起動時に中断したコピージョブ */
+export interface InterruptedJob {
+  id: string;
+  sources: string[];
+  destination: string;
+  strategy: string | null;
+  filesDone: number;
+}
+
+export interface MovedItem {
+  from: string;
+  to: string;
+}
+
+/** 元に戻せるファイル操作 */
+export type Operation =
+  | { type: "rename"; from: string; to: string }
+  | { type: "move"; items: MovedItem[] }
+  | { type: "batchRename"; items: MovedItem[] }
+  | { type: "copy"; created: string[] }
+  | { type: "createDirectory"; path: string }
+  | { type: "createFile"; path: string }
+  | { type: "trash"; paths: string[] };
+
+export interface HistoryEntry {
+  id: number;
+  operation: Operation;
+  /** 操作日時（UNIX エポックからのミリ秒） */
+  timestamp: number;
+}
+
+/** 操作履歴（どちらも新しい順） */
+export interface HistoryView {
+  undo: HistoryEntry[];
+  redo: HistoryEntry[];
+}
+
+/** ゴミ箱内の項目 */
+export interface TrashEntry {
+  id: string;
+  name: string;
+  originalPath: string;
+  /** 削除日時（UNIX エポックからのミリ秒） */
+  deletedAt: number | null;
+  isDir: boolean;
+  /** ファイルのバイト数（フォルダは null） */
+  size: number | null;
+  /** フォルダ直下の項目数（ファイルは null） */
+  itemCount: number | null;
+}
+
+/**
+ * 一括名前変更のルール。適用順は 置換 → 接頭辞・接尾辞 → 大文字小文字 → 拡張子。
+ * 置換後の文字列・接頭辞・接尾辞では {n}（連番）と {date}・{date:%Y%m%d}（更新日時）が使える
+ */
+export interface RenameRules {
+  findReplace?: FindReplace | null;
+  prefix?: string;
+  suffix?: string;
+  counter?: CounterRule;
+  case?: CaseConversion | null;
+  /** 新しい拡張子（空文字で拡張子を外す） */
+  extension?: string | null;
+}
+
+export interface FindReplace {
+  find: string;
+  /** 正規表現では $1・${name} でキャプチャを参照できる */
+  replace: string;
+  regex?: boolean;
+  caseSensitive?: boolean;
+}
+
+export interface CounterRule {
+  start?: number;
+  step?: number;
+  /** ゼロ埋めする桁数（32 桁まで） */
+  padding?: number;
+}
+
+export type CaseConversion = "lower" | "upper" | "title";
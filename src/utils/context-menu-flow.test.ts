@@ -8,7 +8,8 @@
 import { describe, expect, it, vi, beforeEach } from "vitest";
 
 vi.mock("../commands/fs-commands", () => ({
-  readDirectory: vi.fn(),
+  readDirectoryStream: vi.fn(),
+  cancelDirectoryStream: vi.fn(),
   getHomeDir: vi.fn(),
   createDirectory: vi.fn(),
   renameItem: vi.fn(),
@@ -49,6 +50,9 @@ function makeEntry(overrides: Partial<FileEntry> = {}): FileEntry {
     isHidden: false,
     size: 100,
     modified: "2026-01-01 00:00",
+    modifiedAt: null,
+    createdAt: null,
+    accessedAt: null,
     mimeType: "text/plain",
     ...overrides,
   };
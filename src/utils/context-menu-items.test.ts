@@ -11,6 +11,9 @@ function makeEntry(overrides: Partial<FileEntry> = {}): FileEntry {
     isHidden: false,
     size: 100,
     modified: "2026-01-01 00:00",
+    modifiedAt: null,
+    createdAt: null,
+    accessedAt: null,
     mimeType: "text/plain",
     ...overrides,
   };
@@ -5,6 +5,7 @@ import {
   copyItemsWithStrategy,
 } from "../commands/fs-commands";
 import type { ConflictStrategy } from "../components/CopyConflictDialog";
+import type { CopyConflict } from "../types";
 
 export interface PasteRequest {
   paths: string[];
@@ -14,6 +15,8 @@ export interface PasteRequest {
 
 export interface PasteResult {
   conflicts: string[];
+  /** サイズ・更新日時を含む衝突の詳細 */
+  details: CopyConflict[];
   request: PasteRequest;
 }
 
@@ -21,21 +24,19 @@ export interface PasteResult {
 export async function pasteWithConflictCheck(
   req: PasteRequest
 ): Promise<PasteResult | null> {
-  if (req.mode === "cut") {
-    // Move never auto-renames — just move
-    await moveItems(req.paths, req.destination);
-    return null;
-  }
-
-  // Check for conflicts
-  const conflicts = await checkCopyConflicts(req.paths, req.destination);
-  if (conflicts.length === 0) {
-    await copyItems(req.paths, req.destination);
+  // Check for conflicts (same check applies to move)
+  const details = await checkCopyConflicts(req.paths, req.destination);
+  if (details.length === 0) {
+    if (req.mode === "cut") {
+      await moveItems(req.paths, req.destination);
+    } else {
+      await copyItems(req.paths, req.destination);
+    }
     return null;
   }
 
   // Return conflicts for UI to resolve
-  return { conflicts, request: req };
+  return { conflicts: details.map((c) => c.name), details, request: req };
 }
 
 /** Execute paste with a resolved strategy */
@@ -43,5 +44,9 @@ export async function executePasteWithStrategy(
   req: PasteRequest,
   strategy: ConflictStrategy
 ): Promise<void> {
+  if (req.mode === "cut") {
+    await moveItems(req.paths, req.destination, strategy);
+    return;
+  }
   await copyItemsWithStrategy(req.paths, req.destination, strategy);
 }
@@ -1,5 +1,5 @@
 import { describe, it, expect } from "vitest";
-import { sortEntries } from "./sort";
+import { sortEntries, toListOptions } from "./sort";
 import type { FileEntry } from "../types";
 
 function makeEntry(overrides: Partial<FileEntry> = {}): FileEntry {
@@ -11,6 +11,9 @@ function makeEntry(overrides: Partial<FileEntry> = {}): FileEntry {
     isHidden: false,
     size: 100,
     modified: "2024-01-01 00:00",
+    modifiedAt: null,
+    createdAt: null,
+    accessedAt: null,
     mimeType: "text/plain",
     ...overrides,
   };
@@ -52,6 +55,16 @@ describe("sortEntries", () => {
       const result = sortEntries(entries, { key: "name", order: "desc" });
       expect(result.map((e) => e.name)).toEqual(["Charlie", "Bravo", "alpha"]);
     });
+
+    it("名前の中の数字は数値として比べる", () => {
+      const numbered = [
+        makeEntry({ name: "file10.txt", path: "/file10.txt" }),
+        makeEntry({ name: "file2.txt", path: "/file2.txt" }),
+        makeEntry({ name: "file1.txt", path: "/file1.txt" }),
+      ];
+      const result = sortEntries(numbered, { key: "name", order: "asc" });
+      expect(result.map((e) => e.name)).toEqual(["file1.txt", "file2.txt", "file10.txt"]);
+    });
   });
 
   describe("サイズソート", () => {
@@ -74,9 +87,9 @@ describe("sortEntries", () => {
 
   describe("日付ソート", () => {
     const entries = [
-      makeEntry({ name: "new", path: "/new", modified: "2024-03-01 00:00" }),
-      makeEntry({ name: "old", path: "/old", modified: "2024-01-01 00:00" }),
-      makeEntry({ name: "mid", path: "/mid", modified: "2024-02-01 00:00" }),
+      makeEntry({ name: "new", path: "/new", modifiedAt: Date.UTC(2024, 2, 1) }),
+      makeEntry({ name: "old", path: "/old", modifiedAt: Date.UTC(2024, 0, 1) }),
+      makeEntry({ name: "mid", path: "/mid", modifiedAt: Date.UTC(2024, 1, 1) }),
     ];
 
     it("asc: 古い順", () => {
@@ -89,16 +102,38 @@ describe("sortEntries", () => {
       expect(result.map((e) => e.name)).toEqual(["new", "mid", "old"]);
     });
 
+    it("表示が同じ分でも秒の違いで並ぶ", () => {
+      const base = Date.UTC(2024, 0, 1, 10, 30);
+      const sameMinute = [
+        makeEntry({ name: "later", path: "/later", modifiedAt: base + 40_000 }),
+        makeEntry({ name: "earlier", path: "/earlier", modifiedAt: base + 5_000 }),
+      ];
+      const result = sortEntries(sameMinute, { key: "modified", order: "asc" });
+      expect(result.map((e) => e.name)).toEqual(["earlier", "later"]);
+    });
+
     it("null は先頭に来る（asc時の特徴テスト）", () => {
       const withNull = [
-        makeEntry({ name: "dated", path: "/dated", modified: "2024-01-01 00:00" }),
-        makeEntry({ name: "nodate", path: "/nodate", modified: null }),
+        makeEntry({ name: "dated", path: "/dated", modifiedAt: Date.UTC(2024, 0, 1) }),
+        makeEntry({ name: "nodate", path: "/nodate", modifiedAt: null }),
       ];
       const result = sortEntries(withNull, { key: "modified", order: "asc" });
       expect(result[0].name).toBe("nodate");
     });
   });
 
+  describe("種類ソート", () => {
+    it("拡張子順、同じ拡張子は名前順", () => {
+      const entries = [
+        makeEntry({ name: "b.txt", path: "/b.txt" }),
+        makeEntry({ name: "z.md", path: "/z.md" }),
+        makeEntry({ name: "a.txt", path: "/a.txt" }),
+      ];
+      const result = sortEntries(entries, { key: "type", order: "asc" });
+      expect(result.map((e) => e.name)).toEqual(["z.md", "a.txt", "b.txt"]);
+    });
+  });
+
   describe("非破壊", () => {
     it("元配列を変更しない", () => {
       const entries = [
@@ -110,4 +145,15 @@ describe("sortEntries", () => {
       expect(entries).toEqual(original);
     });
   });
+
+  describe("toListOptions", () => {
+    it("ソート設定と隠しファイルの表示を Rust 側のオプションにする", () => {
+      expect(toListOptions({ key: "modified", order: "desc" }, true)).toEqual({
+        sortBy: "modified",
+        descending: true,
+        dirsFirst: true,
+        showHidden: true,
+      });
+    });
+  });
 });
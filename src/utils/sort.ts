@@ -1,20 +1,41 @@
-import type { FileEntry, SortConfig } from "../types";
+import type { FileEntry, ListOptions, SortConfig } from "../types";
 
+/** 一覧の並べ替え・隠しファイルの絞り込みは Rust 側で行う（ストリームで届く順がそのまま表示順） */
+export function toListOptions(config: SortConfig, showHidden: boolean): ListOptions {
+  return {
+    sortBy: config.key,
+    descending: config.order === "desc",
+    dirsFirst: true,
+    showHidden,
+  };
+}
+
+function extensionOf(entry: FileEntry): string {
+  if (entry.isDir) return "";
+  const dot = entry.name.lastIndexOf(".");
+  return dot > 0 ? entry.name.slice(dot + 1).toLowerCase() : "";
+}
+
+/** Rust 側と同じく数字を数値として比べ、同じ値どうしは名前順にする */
 export function sortEntries(entries: FileEntry[], config: SortConfig): FileEntry[] {
   const sorted = [...entries];
   sorted.sort((a, b) => {
     if (a.isDir !== b.isDir) return a.isDir ? -1 : 1;
 
+    const byName = a.name.localeCompare(b.name, undefined, { sensitivity: "base", numeric: true });
     let cmp = 0;
     switch (config.key) {
       case "name":
-        cmp = a.name.localeCompare(b.name, undefined, { sensitivity: "base" });
+        cmp = byName;
         break;
       case "size":
-        cmp = a.size - b.size;
+        cmp = a.size - b.size || byName;
         break;
       case "modified":
-        cmp = (a.modified || "").localeCompare(b.modified || "");
+        cmp = (a.modifiedAt ?? 0) - (b.modifiedAt ?? 0) || byName;
+        break;
+      case "type":
+        cmp = extensionOf(a).localeCompare(extensionOf(b)) || byName;
         break;
     }
     return config.order === "asc" ? cmp : -cmp;